@@ -0,0 +1,100 @@
+//! Compares loading poll results with one query per proposal and vote against
+//! the joined query used by `find_poll_results`.
+//!
+//! Usage: `cargo run --release --example results_benchmark [items] [voters]`
+
+extern crate slackbot_rs;
+
+use std::env;
+use std::time::{Duration, Instant};
+
+use slackbot_rs::models::*;
+use slackbot_rs::repository::{PollRepository, SqliteRepository};
+use slackbot_rs::transport::RecordingTransport;
+use slackbot_rs::{item, poll, vote, voter};
+
+const RUNS: u32 = 10;
+
+fn main() {
+    let args: Vec<usize> = env::args().skip(1).filter_map(|arg| arg.parse().ok()).collect();
+    let num_items = args.get(0).cloned().unwrap_or(100);
+    let num_voters = args.get(1).cloned().unwrap_or(50);
+
+    let repo = SqliteRepository::in_memory();
+
+    println!("Creating a poll with {} proposals and {} voters...", num_items, num_voters);
+    let poll = create_synthetic_poll(&repo, num_items, num_voters);
+
+    let per_row = measure(|| load_results_per_row(&repo, &poll));
+    let joined = measure(|| repo.find_poll_results(&poll).len());
+
+    println!("One query per proposal and vote: {:>8.2} ms", as_millis(per_row));
+    println!("Joined query:                    {:>8.2} ms", as_millis(joined));
+}
+
+fn create_synthetic_poll(repo: &PollRepository, num_items: usize, num_voters: usize) -> Poll {
+    poll::create_poll(repo, "benchmark", None, None);
+    poll::start_poll(repo, "benchmark");
+
+    let poll = repo.find_poll_by_name("benchmark").unwrap();
+    let transport = RecordingTransport::new();
+
+    let items: Vec<_> = (0..num_items)
+        .map(|num| {
+            let name = format!("item{}", num);
+            item::create_item(repo, &name);
+            repo.find_item_by_name(&name).unwrap()
+        })
+        .collect();
+
+    let voters: Vec<_> = (0..num_voters)
+        .map(|num| {
+            let slack_id = format!("U{}", num);
+            voter::create_voter(repo, &slack_id, &format!("voter{}", num));
+            repo.find_voter_by_slack_id(&slack_id).unwrap()
+        })
+        .collect();
+
+    for (item_num, item) in items.iter().enumerate() {
+        let proposal = vote::find_or_create_proposal(repo, &poll, item).unwrap();
+
+        for (voter_num, voter) in voters.iter().enumerate() {
+            let weight = if (item_num + voter_num) % 3 == 0 { -1 } else { 1 };
+            vote::set_vote(repo, &transport, voter.id, proposal.id, weight);
+        }
+    }
+
+    poll
+}
+
+/// How results were loaded before there was `find_poll_results`.
+fn load_results_per_row(repo: &PollRepository, poll: &Poll) -> usize {
+    let mut results = Vec::new();
+
+    for proposal in repo.find_proposals_by_poll(poll) {
+        if let Some(item) = repo.find_item_by_id(proposal.item_id) {
+            let votes: Vec<_> = repo.find_votes_by_proposal(&proposal)
+                .into_iter()
+                .filter_map(|vote| repo.find_voter_by_id(vote.voter_id).map(|voter| (vote, voter)))
+                .collect();
+
+            results.push((item, votes));
+        }
+    }
+
+    results.len()
+}
+
+fn measure<F: Fn() -> usize>(load: F) -> Duration {
+    let start = Instant::now();
+
+    for _ in 0..RUNS {
+        load();
+    }
+
+    start.elapsed() / RUNS
+}
+
+fn as_millis(duration: Duration) -> f64 {
+    (duration.as_secs() as f64) * 1000.0 + (duration.subsec_nanos() as f64) / 1_000_000.0
+}
@@ -0,0 +1,9 @@
+DROP TABLE IF EXISTS votes;
+
+DROP TABLE IF EXISTS proposals;
+
+DROP TABLE IF EXISTS voters;
+
+DROP TABLE IF EXISTS items;
+
+DROP TABLE IF EXISTS polls;
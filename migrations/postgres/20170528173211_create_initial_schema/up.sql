@@ -0,0 +1,31 @@
+CREATE TABLE polls (
+  id SERIAL PRIMARY KEY,
+  name VARCHAR UNIQUE NOT NULL,
+  status VARCHAR NOT NULL,
+  started_at VARCHAR,
+  concluded_at VARCHAR
+);
+
+CREATE TABLE items (
+  id SERIAL PRIMARY KEY,
+  name VARCHAR UNIQUE NOT NULL
+);
+
+CREATE TABLE voters (
+  id SERIAL PRIMARY KEY,
+  name VARCHAR UNIQUE NOT NULL,
+  slack_id VARCHAR
+);
+
+CREATE TABLE proposals (
+  id SERIAL PRIMARY KEY,
+  poll_id INTEGER NOT NULL REFERENCES polls(id),
+  item_id INTEGER NOT NULL REFERENCES items(id)
+);
+
+CREATE TABLE votes (
+  id SERIAL PRIMARY KEY,
+  voter_id INTEGER NOT NULL REFERENCES voters(id),
+  proposal_id INTEGER NOT NULL REFERENCES proposals(id),
+  weight INTEGER NOT NULL
+);
@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS voters_slack_id;
+DROP INDEX IF EXISTS votes_proposal_id;
+
+DROP INDEX IF EXISTS votes_voter_id_proposal_id;
+DROP INDEX IF EXISTS proposals_poll_id_item_id;
@@ -0,0 +1,5 @@
+CREATE UNIQUE INDEX proposals_poll_id_item_id ON proposals (poll_id, item_id);
+CREATE UNIQUE INDEX votes_voter_id_proposal_id ON votes (voter_id, proposal_id);
+
+CREATE INDEX votes_proposal_id ON votes (proposal_id);
+CREATE INDEX voters_slack_id ON voters (slack_id);
@@ -0,0 +1,2 @@
+ALTER TABLE polls DROP COLUMN creator_slack_id;
+ALTER TABLE polls DROP COLUMN visibility;
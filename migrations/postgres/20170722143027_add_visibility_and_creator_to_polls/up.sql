@@ -0,0 +1,2 @@
+ALTER TABLE polls ADD COLUMN visibility VARCHAR NOT NULL DEFAULT 'ALWAYS';
+ALTER TABLE polls ADD COLUMN creator_slack_id VARCHAR;
@@ -0,0 +1,2 @@
+ALTER TABLE polls DROP COLUMN max_approvals;
+ALTER TABLE polls DROP COLUMN budget;
@@ -0,0 +1,2 @@
+ALTER TABLE polls ADD COLUMN budget INTEGER;
+ALTER TABLE polls ADD COLUMN max_approvals INTEGER;
@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS poll_participants;
+
+ALTER TABLE polls DROP COLUMN channel_id;
+ALTER TABLE polls DROP COLUMN eligibility;
@@ -0,0 +1,10 @@
+ALTER TABLE polls ADD COLUMN eligibility VARCHAR NOT NULL DEFAULT 'REGISTERED';
+ALTER TABLE polls ADD COLUMN channel_id VARCHAR;
+
+CREATE TABLE poll_participants (
+  id SERIAL PRIMARY KEY,
+  poll_id INTEGER NOT NULL REFERENCES polls(id),
+  voter_id INTEGER NOT NULL REFERENCES voters(id)
+);
+
+CREATE UNIQUE INDEX poll_participants_poll_id_voter_id ON poll_participants (poll_id, voter_id);
@@ -0,0 +1,2 @@
+ALTER TABLE polls DROP COLUMN auto_conclude;
+ALTER TABLE polls DROP COLUMN quorum;
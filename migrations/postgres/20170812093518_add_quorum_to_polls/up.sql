@@ -0,0 +1,2 @@
+ALTER TABLE polls ADD COLUMN quorum INTEGER;
+ALTER TABLE polls ADD COLUMN auto_conclude VARCHAR NOT NULL DEFAULT 'OFF';
@@ -0,0 +1,7 @@
+ALTER TABLE items DROP COLUMN tags;
+ALTER TABLE items DROP COLUMN vegan;
+ALTER TABLE items DROP COLUMN vegetarian;
+ALTER TABLE items DROP COLUMN menu_url;
+ALTER TABLE items DROP COLUMN price_level;
+ALTER TABLE items DROP COLUMN address;
+ALTER TABLE items DROP COLUMN category;
@@ -0,0 +1,7 @@
+CREATE TABLE item_aliases (
+  id SERIAL PRIMARY KEY,
+  item_id INTEGER NOT NULL REFERENCES items(id),
+  alias VARCHAR NOT NULL
+);
+
+CREATE UNIQUE INDEX item_aliases_alias ON item_aliases (alias);
@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS items_normalized_name;
+DROP INDEX IF EXISTS polls_normalized_name;
+
+ALTER TABLE items DROP COLUMN normalized_name;
+ALTER TABLE polls DROP COLUMN normalized_name;
@@ -0,0 +1,6 @@
+-- Names as folded by `matching::normalize`, filled in by the bot for existing rows
+ALTER TABLE polls ADD COLUMN normalized_name VARCHAR;
+ALTER TABLE items ADD COLUMN normalized_name VARCHAR;
+
+CREATE INDEX polls_normalized_name ON polls (normalized_name);
+CREATE INDEX items_normalized_name ON items (normalized_name);
@@ -0,0 +1,24 @@
+-- Point votes of duplicated proposals to the oldest proposal of the same poll and item
+UPDATE votes SET proposal_id = (
+  SELECT MIN(canonical.id)
+  FROM proposals AS canonical, proposals AS current
+  WHERE current.id = votes.proposal_id
+    AND canonical.poll_id = current.poll_id
+    AND canonical.item_id = current.item_id
+)
+WHERE proposal_id IN (SELECT id FROM proposals);
+
+DELETE FROM proposals WHERE id NOT IN (
+  SELECT MIN(id) FROM proposals GROUP BY poll_id, item_id
+);
+
+-- Keep the latest vote of a voter per proposal
+DELETE FROM votes WHERE id NOT IN (
+  SELECT MAX(id) FROM votes GROUP BY voter_id, proposal_id
+);
+
+CREATE UNIQUE INDEX proposals_poll_id_item_id ON proposals (poll_id, item_id);
+CREATE UNIQUE INDEX votes_voter_id_proposal_id ON votes (voter_id, proposal_id);
+
+CREATE INDEX votes_proposal_id ON votes (proposal_id);
+CREATE INDEX voters_slack_id ON voters (slack_id);
@@ -0,0 +1,9 @@
+CREATE TABLE settings (
+	id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+  scope VARCHAR NOT NULL,
+  scope_id VARCHAR NOT NULL,
+  name VARCHAR NOT NULL,
+  value VARCHAR NOT NULL
+);
+
+CREATE UNIQUE INDEX settings_scope_scope_id_name ON settings (scope, scope_id, name);
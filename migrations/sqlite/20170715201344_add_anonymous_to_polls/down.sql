@@ -0,0 +1,15 @@
+-- SQLite cannot drop columns, so the table is copied without it
+ALTER TABLE polls RENAME TO polls_old;
+
+CREATE TABLE polls (
+	id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+	name VARCHAR UNIQUE NOT NULL,
+  status VARCHAR NOT NULL,
+  started_at VARCHAR,
+  concluded_at VARCHAR
+);
+
+INSERT INTO polls (id, name, status, started_at, concluded_at)
+  SELECT id, name, status, started_at, concluded_at FROM polls_old;
+
+DROP TABLE polls_old;
@@ -0,0 +1 @@
+ALTER TABLE polls ADD COLUMN anonymous BOOLEAN NOT NULL DEFAULT 0;
@@ -0,0 +1,16 @@
+-- SQLite cannot drop columns, so the table is copied without them
+ALTER TABLE polls RENAME TO polls_old;
+
+CREATE TABLE polls (
+	id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+	name VARCHAR UNIQUE NOT NULL,
+  status VARCHAR NOT NULL,
+  started_at VARCHAR,
+  concluded_at VARCHAR,
+  anonymous BOOLEAN NOT NULL DEFAULT 0
+);
+
+INSERT INTO polls (id, name, status, started_at, concluded_at, anonymous)
+  SELECT id, name, status, started_at, concluded_at, anonymous FROM polls_old;
+
+DROP TABLE polls_old;
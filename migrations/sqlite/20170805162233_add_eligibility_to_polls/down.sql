@@ -0,0 +1,22 @@
+DROP TABLE IF EXISTS poll_participants;
+
+-- SQLite cannot drop columns, so the table is copied without them
+ALTER TABLE polls RENAME TO polls_old;
+
+CREATE TABLE polls (
+	id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+	name VARCHAR UNIQUE NOT NULL,
+  status VARCHAR NOT NULL,
+  started_at VARCHAR,
+  concluded_at VARCHAR,
+  anonymous BOOLEAN NOT NULL DEFAULT 0,
+  visibility VARCHAR NOT NULL DEFAULT 'ALWAYS',
+  creator_slack_id VARCHAR,
+  budget INTEGER,
+  max_approvals INTEGER
+);
+
+INSERT INTO polls (id, name, status, started_at, concluded_at, anonymous, visibility, creator_slack_id, budget, max_approvals)
+  SELECT id, name, status, started_at, concluded_at, anonymous, visibility, creator_slack_id, budget, max_approvals FROM polls_old;
+
+DROP TABLE polls_old;
@@ -0,0 +1,12 @@
+ALTER TABLE polls ADD COLUMN eligibility VARCHAR NOT NULL DEFAULT 'REGISTERED';
+ALTER TABLE polls ADD COLUMN channel_id VARCHAR;
+
+CREATE TABLE poll_participants (
+	id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+  poll_id INTEGER NOT NULL,
+  voter_id INTEGER NOT NULL,
+  FOREIGN KEY(poll_id) REFERENCES polls(id),
+  FOREIGN KEY(voter_id) REFERENCES voters(id)
+);
+
+CREATE UNIQUE INDEX poll_participants_poll_id_voter_id ON poll_participants (poll_id, voter_id);
@@ -0,0 +1,11 @@
+-- SQLite cannot drop columns, so the table is copied without them
+ALTER TABLE items RENAME TO items_old;
+
+CREATE TABLE items (
+	id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+	name VARCHAR UNIQUE NOT NULL
+);
+
+INSERT INTO items (id, name) SELECT id, name FROM items_old;
+
+DROP TABLE items_old;
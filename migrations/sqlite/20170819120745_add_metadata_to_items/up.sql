@@ -0,0 +1,8 @@
+ALTER TABLE items ADD COLUMN category VARCHAR;
+ALTER TABLE items ADD COLUMN address VARCHAR;
+ALTER TABLE items ADD COLUMN price_level INTEGER;
+ALTER TABLE items ADD COLUMN menu_url VARCHAR;
+ALTER TABLE items ADD COLUMN vegetarian BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE items ADD COLUMN vegan BOOLEAN NOT NULL DEFAULT 0;
+-- Comma separated with a leading and trailing comma, e.g. ',veggie,cheap,'
+ALTER TABLE items ADD COLUMN tags VARCHAR NOT NULL DEFAULT '';
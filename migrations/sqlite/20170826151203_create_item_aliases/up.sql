@@ -0,0 +1,8 @@
+CREATE TABLE item_aliases (
+	id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+  item_id INTEGER NOT NULL,
+  alias VARCHAR NOT NULL,
+  FOREIGN KEY(item_id) REFERENCES items(id)
+);
+
+CREATE UNIQUE INDEX item_aliases_alias ON item_aliases (alias);
@@ -0,0 +1,13 @@
+CREATE TABLE audit_log (
+	id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+  created_at VARCHAR NOT NULL,
+  slack_id VARCHAR,
+  voter_id INTEGER,
+  channel_id VARCHAR,
+  command VARCHAR NOT NULL,
+  parameters VARCHAR NOT NULL,
+  outcome VARCHAR NOT NULL,
+  poll_id INTEGER
+);
+
+CREATE INDEX audit_log_poll_id ON audit_log (poll_id);
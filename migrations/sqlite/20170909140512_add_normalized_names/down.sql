@@ -0,0 +1,46 @@
+-- SQLite cannot drop columns, so the tables are copied without them
+DROP INDEX IF EXISTS items_normalized_name;
+DROP INDEX IF EXISTS polls_normalized_name;
+
+ALTER TABLE polls RENAME TO polls_old;
+
+CREATE TABLE polls (
+	id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+	name VARCHAR UNIQUE NOT NULL,
+  status VARCHAR NOT NULL,
+  started_at VARCHAR,
+  concluded_at VARCHAR,
+  anonymous BOOLEAN NOT NULL DEFAULT 0,
+  visibility VARCHAR NOT NULL DEFAULT 'ALWAYS',
+  creator_slack_id VARCHAR,
+  budget INTEGER,
+  max_approvals INTEGER,
+  eligibility VARCHAR NOT NULL DEFAULT 'REGISTERED',
+  channel_id VARCHAR,
+  quorum INTEGER,
+  auto_conclude VARCHAR NOT NULL DEFAULT 'OFF'
+);
+
+INSERT INTO polls (id, name, status, started_at, concluded_at, anonymous, visibility, creator_slack_id, budget, max_approvals, eligibility, channel_id, quorum, auto_conclude)
+  SELECT id, name, status, started_at, concluded_at, anonymous, visibility, creator_slack_id, budget, max_approvals, eligibility, channel_id, quorum, auto_conclude FROM polls_old;
+
+DROP TABLE polls_old;
+
+ALTER TABLE items RENAME TO items_old;
+
+CREATE TABLE items (
+	id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+	name VARCHAR UNIQUE NOT NULL,
+  category VARCHAR,
+  address VARCHAR,
+  price_level INTEGER,
+  menu_url VARCHAR,
+  vegetarian BOOLEAN NOT NULL DEFAULT 0,
+  vegan BOOLEAN NOT NULL DEFAULT 0,
+  tags VARCHAR NOT NULL DEFAULT ''
+);
+
+INSERT INTO items (id, name, category, address, price_level, menu_url, vegetarian, vegan, tags)
+  SELECT id, name, category, address, price_level, menu_url, vegetarian, vegan, tags FROM items_old;
+
+DROP TABLE items_old;
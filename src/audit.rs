@@ -0,0 +1,89 @@
+use command::Context;
+use models::{AuditEntry, NewAuditEntry, Poll};
+use poll;
+use repository::PollRepository;
+use timestamp;
+use voter;
+
+/// Parameters longer than this, e.g. files given to `!import_items`, are cut off.
+const MAX_PARAMETERS_LENGTH: usize = 500;
+
+/// Whether a command is recorded in the audit log and what it is about.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AuditScope<'p> {
+    /// Nothing is recorded, the command does not change anything.
+    None,
+    /// The command changes items, voters or settings.
+    Global,
+    /// The command changes the poll with this name.
+    Poll(&'p str)
+}
+
+impl<'p> AuditScope<'p> {
+    /// For commands that take the name of the poll they change as first parameter.
+    pub fn first_poll(parameters: &[&'p str]) -> AuditScope<'p> {
+        match parameters.first() {
+            Some(&poll_name) => AuditScope::Poll(poll_name),
+            None => AuditScope::Global
+        }
+    }
+}
+
+/// Records that the user of `context` sent `command`. The outcome are the
+/// messages the command answered with, see `Context::answered`. Commands sent
+/// without enough parameters did nothing and are not recorded. A vote in a
+/// secret poll is recorded without the voter, the channel and the choice.
+pub fn record(context: &Context, command: &str, parameters: &[&str], enough_params: bool, scope: AuditScope) -> bool {
+    if !enough_params {
+        return false;
+    }
+
+    let poll = match scope {
+        AuditScope::None => return false,
+        AuditScope::Global => None,
+        // Looked up afterwards, so polls created by the command are found as well
+        AuditScope::Poll(poll_name) => poll::find_poll_by_name(context.repo, poll_name)
+    };
+
+    let secret_vote = command == "vote" && poll.as_ref().map_or(false, |poll| poll.anonymous);
+
+    let (slack_id, voter_id, channel_id, parameters) = if secret_vote {
+        (None, None, None, &parameters[..1])
+    } else {
+        let voter_id = context.user.as_ref()
+            .and_then(|user| voter::find_voter_by_user(context.repo, user))
+            .map(|voter| voter.id);
+
+        (context.user_id(), voter_id, context.channel.as_ref().map(String::as_str), parameters)
+    };
+
+    let answered = context.answered();
+    let outcome = if answered.is_empty() { String::from("ok") } else { answered.join(", ") };
+
+    let created_at = timestamp::now();
+    let parameters = truncate(&parameters.join(" "), MAX_PARAMETERS_LENGTH);
+
+    context.repo.insert_audit_entry(&NewAuditEntry {
+        created_at: created_at.as_str(),
+        slack_id: slack_id,
+        voter_id: voter_id,
+        channel_id: channel_id,
+        command: command,
+        parameters: parameters.as_str(),
+        outcome: outcome.as_str(),
+        poll_id: poll.map(|poll| poll.id)
+    })
+}
+
+/// Everything that was done to `poll`, oldest first.
+pub fn find_poll_history(repo: &PollRepository, poll: &Poll) -> Vec<AuditEntry> {
+    repo.find_audit_entries_by_poll(poll.id)
+}
+
+fn truncate(text: &str, max_length: usize) -> String {
+    if text.chars().count() <= max_length {
+        text.to_owned()
+    } else {
+        format!("{}…", text.chars().take(max_length).collect::<String>())
+    }
+}
@@ -0,0 +1,166 @@
+use chrono::Utc;
+use libsqlite3_sys as ffi;
+
+use std::ffi::{CStr, CString};
+use std::fs::{self, File};
+use std::io::Write;
+use std::os::raw::c_int;
+use std::path::{Path, PathBuf};
+use std::ptr;
+
+use export::{self, ExportFormat};
+use poll;
+use repository::{self, PollRepository};
+
+/// How often a step of the backup is retried while the bot holds a lock on the database.
+const MAX_BUSY_RETRIES: usize = 50;
+const BUSY_SLEEP_MILLIS: c_int = 100;
+
+/// The file behind an SQLite `DATABASE_URL`, `None` for PostgreSQL, which
+/// is backed up with its own tools like `pg_dump`.
+pub fn database_path(database_url: &str) -> Option<&str> {
+    if repository::is_postgres_url(database_url) || database_url == ":memory:" {
+        None
+    } else {
+        Some(database_url.trim_left_matches("sqlite://"))
+    }
+}
+
+/// Copies the database into a new timestamped file in `backup_dir`, e.g.
+/// `lunch-20170902-123000.sqlite`, and returns its path. The bot may keep
+/// running meanwhile.
+pub fn backup(database_url: &str, backup_dir: &Path) -> Result<PathBuf, String> {
+    let database = database_path(database_url).ok_or_else(|| String::from("Only SQLite databases can be backed up."))?;
+
+    fs::create_dir_all(backup_dir).map_err(|err| format!("Cannot create {}: {}", backup_dir.display(), err))?;
+
+    let target = backup_dir.join(format!("lunch-{}.sqlite", Utc::now().format("%Y%m%d-%H%M%S")));
+    copy_database(database, &target.to_string_lossy())?;
+
+    Ok(target)
+}
+
+/// Replaces the database with the backup in `backup_file`. Refuses while
+/// another connection, e.g. the bot in the middle of a command, holds a lock on
+/// the database. The copy itself locks the database exclusively.
+pub fn restore(backup_file: &Path, database_url: &str) -> Result<(), String> {
+    let database = database_path(database_url).ok_or_else(|| String::from("Only SQLite databases can be restored."))?;
+
+    if !backup_file.exists() {
+        return Err(format!("{} does not exist.", backup_file.display()));
+    }
+
+    check_unlocked(database)?;
+    copy_database(&backup_file.to_string_lossy(), database)
+}
+
+/// Deletes the polls concluded more than `days` days ago. With an `archive_dir`
+/// they are exported as JSON into a timestamped file there first, which
+/// `import history` can read again. Returns the number of deleted polls.
+pub fn prune_polls(repo: &PollRepository, days: i64, archive_dir: Option<&Path>) -> Result<usize, String> {
+    let polls = poll::find_expired_polls(repo, days);
+
+    if polls.is_empty() {
+        return Ok(0);
+    }
+
+    if let Some(archive_dir) = archive_dir {
+        fs::create_dir_all(archive_dir).map_err(|err| format!("Cannot create {}: {}", archive_dir.display(), err))?;
+
+        let archive = archive_dir.join(format!("archive-{}.json", Utc::now().format("%Y%m%d-%H%M%S")));
+
+        File::create(&archive)
+            .and_then(|mut file| file.write_all(export::export(repo, &polls, ExportFormat::Json).as_bytes()))
+            .map_err(|err| format!("Cannot write {}: {}", archive.display(), err))?;
+    }
+
+    for poll in polls.iter() {
+        repo.delete_poll(poll.id);
+    }
+
+    Ok(polls.len())
+}
+
+/// Copies all pages of the database `source` to `target` with SQLite's
+/// online backup API, which gives a consistent copy even while others write.
+pub fn copy_database(source: &str, target: &str) -> Result<(), String> {
+    unsafe {
+        let source_db = open(source)?;
+
+        let target_db = match open(target) {
+            Ok(target_db) => target_db,
+            Err(err) => {
+                ffi::sqlite3_close(source_db);
+                return Err(err);
+            }
+        };
+
+        let main = CString::new("main").unwrap();
+        let backup = ffi::sqlite3_backup_init(target_db, main.as_ptr(), source_db, main.as_ptr());
+
+        let result = if backup.is_null() {
+            Err(error_message(target_db))
+        } else {
+            let mut rc = ffi::sqlite3_backup_step(backup, -1);
+            let mut retries = 0;
+
+            while (rc == ffi::SQLITE_BUSY || rc == ffi::SQLITE_LOCKED) && retries < MAX_BUSY_RETRIES {
+                ffi::sqlite3_sleep(BUSY_SLEEP_MILLIS);
+                rc = ffi::sqlite3_backup_step(backup, -1);
+                retries += 1;
+            }
+
+            ffi::sqlite3_backup_finish(backup);
+
+            if rc == ffi::SQLITE_DONE {
+                Ok(())
+            } else {
+                Err(error_message(target_db))
+            }
+        };
+
+        ffi::sqlite3_close(target_db);
+        ffi::sqlite3_close(source_db);
+
+        result
+    }
+}
+
+/// Fails if another connection holds a lock on `database`, without waiting for it.
+fn check_unlocked(database: &str) -> Result<(), String> {
+    unsafe {
+        let db = open(database)?;
+        let begin = CString::new("BEGIN EXCLUSIVE").unwrap();
+
+        let result = match ffi::sqlite3_exec(db, begin.as_ptr(), None, ptr::null_mut(), ptr::null_mut()) {
+            ffi::SQLITE_OK => {
+                let rollback = CString::new("ROLLBACK").unwrap();
+                ffi::sqlite3_exec(db, rollback.as_ptr(), None, ptr::null_mut(), ptr::null_mut());
+                Ok(())
+            },
+            ffi::SQLITE_BUSY | ffi::SQLITE_LOCKED => Err(format!("{} is in use, stop the bot first.", database)),
+            _ => Err(error_message(db))
+        };
+
+        ffi::sqlite3_close(db);
+
+        result
+    }
+}
+
+unsafe fn open(path: &str) -> Result<*mut ffi::sqlite3, String> {
+    let c_path = CString::new(path).map_err(|_| format!("Invalid database path {}.", path))?;
+    let mut db = ptr::null_mut();
+
+    if ffi::sqlite3_open(c_path.as_ptr(), &mut db) == ffi::SQLITE_OK {
+        Ok(db)
+    } else {
+        let err = error_message(db);
+        ffi::sqlite3_close(db);
+        Err(format!("Cannot open {}: {}", path, err))
+    }
+}
+
+unsafe fn error_message(db: *mut ffi::sqlite3) -> String {
+    CStr::from_ptr(ffi::sqlite3_errmsg(db)).to_string_lossy().into_owned()
+}
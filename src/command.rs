@@ -1,20 +1,43 @@
-use diesel::sqlite::{SqliteConnection};
+use audit::{self, AuditScope};
+use i18n::{self, Locale};
+use models::Poll;
+use personality::{self, Personalities};
+use repository::PollRepository;
+use transport::Transport;
 
-use slack::{RtmClient, User};
+use rand::StdRng;
+use slack::User;
 
+use std::cell::{RefCell, RefMut};
 use std::cmp::{PartialEq, Eq};
+use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
 
-pub struct Command<'a> {
-    name: &'a str,
-    callback: Box<Fn(&mut Context, Vec<&str>) -> bool>,
+// TODO: Should come from config
+pub const COMMAND_TOKEN: &'static str = "!";
+
+/// A single bot command like `new_poll` or `vote`.
+pub trait CommandHandler {
+    fn name(&self) -> &'static str;
+
+    /// Executes the command. Returns `false` if there were not enough parameters.
+    fn handle(&self, context: &mut Context, parameters: Vec<&str>) -> bool;
+
+    /// Whether the command is recorded in the audit log. Commands that change
+    /// something have to say so.
+    fn audit_scope<'p>(&self, _parameters: &[&'p str]) -> AuditScope<'p> {
+        AuditScope::None
+    }
+}
+
+pub struct Command {
+    handler: Box<CommandHandler>,
 }
 
-impl <'a> Command<'a> {
-    pub fn new<'r>(name: &'r str, callback: Box<Fn(&mut Context, Vec<&str>) -> bool>) -> Command<'r> {
+impl Command {
+    pub fn new(handler: Box<CommandHandler>) -> Command {
         Command {
-            name: name,
-            callback: callback
+            handler: handler
         }
     }
 
@@ -28,43 +51,275 @@ impl <'a> Command<'a> {
             }
         }
 
-        println!("[Info] Invoking command {} by {} with parameters {:?}.", self.name, user_name, parameters);
-        (self.callback)(context, parameters)
+        // Not the parameters, they would give away the choices of secret votes
+        println!("[Info] Invoking command {} by {}.", self.name(), user_name);
+
+        let scope = self.handler.audit_scope(&parameters);
+        let enough_params = self.handler.handle(context, parameters.clone());
+
+        audit::record(context, self.name(), &parameters, enough_params, scope);
+
+        enough_params
+    }
+
+    pub fn name(&self) -> &str {
+        self.handler.name()
     }
 
     pub fn matches(&self, name: &str) -> bool {
-        self.name == name
+        self.name() == name
     }
 }
 
-impl <'a> PartialEq for Command<'a> {
+impl PartialEq for Command {
     fn eq(&self, other: &Command) -> bool {
-        self.name == other.name
+        self.name() == other.name()
     }
 }
 
-impl <'a> Eq for Command<'a> {}
+impl Eq for Command {}
 
-impl <'a> Hash for Command<'a> {
+impl Hash for Command {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.name.hash(state)
+        self.name().hash(state)
     }
 }
 
 pub struct Context<'a> {
-    pub db_conn: &'a SqliteConnection,
-    pub cli: &'a RtmClient,
+    pub repo: &'a PollRepository,
+    pub transport: &'a Transport,
     pub channel: &'a Option<String>,
-    pub user: &'a Option<User>
+    pub user: &'a Option<User>,
+    pub personalities: &'a Personalities,
+    /// Slack ids of the users allowed to manage every poll.
+    pub admins: &'a [String],
+    /// The whole message the command was sent with.
+    pub input: &'a str,
+    pub locale: Locale,
+    pub personality: String,
+    /// The ids of the messages answered with so far, the outcome in the audit log.
+    answered: RefCell<Vec<String>>
 }
 
 impl <'a> Context<'a> {
-    pub fn new(db_conn: &'a SqliteConnection, cli: &'a RtmClient, channel: &'a Option<String>, user: &'a Option<User>) -> Context<'a> {
+    pub fn new(repo: &'a PollRepository, transport: &'a Transport, channel: &'a Option<String>, user: &'a Option<User>, personalities: &'a Personalities) -> Context<'a> {
+        let channel_id = channel.as_ref().map(String::as_str);
+        let user_id = user.as_ref().and_then(|user| user.id.as_ref()).map(String::as_str);
+
+        let locale = i18n::resolve_locale(repo, channel_id, user_id);
+        let personality = personality::resolve_personality(repo, channel_id);
+
         Context {
-            db_conn: db_conn,
-            cli: cli,
+            repo: repo,
+            transport: transport,
             channel: channel,
-            user: user
+            user: user,
+            personalities: personalities,
+            admins: &[],
+            input: "",
+            locale: locale,
+            personality: personality,
+            answered: RefCell::new(Vec::new())
+        }
+    }
+
+    pub fn with_admins(mut self, admins: &'a [String]) -> Context<'a> {
+        self.admins = admins;
+        self
+    }
+
+    pub fn with_input(mut self, input: &'a str) -> Context<'a> {
+        self.input = input;
+        self
+    }
+
+    /// Everything after the command name with line breaks kept, for commands
+    /// that take whole files. Slack's formatting and code blocks are removed.
+    pub fn command_text(&self) -> String {
+        let command_line = get_command_line(self.input).unwrap_or_default();
+        let command_line = command_line.trim();
+        let text = command_line.find(char::is_whitespace).map_or("", |end| &command_line[end..]);
+
+        unescape_slack(text.trim().trim_matches('`'))
+    }
+
+    pub fn user_id(&self) -> Option<&str> {
+        self.user.as_ref().and_then(|user| user.id.as_ref()).map(String::as_str)
+    }
+
+    pub fn is_admin(&self) -> bool {
+        match self.user_id() {
+            Some(user_id) => self.admins.iter().any(|admin| admin == user_id),
+            None => false
+        }
+    }
+
+    /// Whether the user created `poll` or is an admin. Polls from before we
+    /// kept track of their creator can be managed by everyone.
+    pub fn can_manage(&self, poll: &Poll) -> bool {
+        if self.is_admin() {
+            return true;
+        }
+
+        match poll.creator_slack_id {
+            Some(ref creator_id) => self.user_id() == Some(creator_id.as_str()),
+            None => true
         }
     }
+
+    /// Whether the command was sent as a direct message to the bot. Slack's
+    /// direct message channel ids start with a `D`.
+    pub fn is_direct_message(&self) -> bool {
+        self.channel.as_ref().map_or(false, |channel_id| channel_id.starts_with("D"))
+    }
+
+    /// For random choices, reproducible with `RANDOM_SEED`.
+    pub fn rng(&self) -> RefMut<StdRng> {
+        self.personalities.rng()
+    }
+
+    /// Translates the message `id` into the locale of this context.
+    pub fn tr(&self, id: &str, args: &[(&str, &str)]) -> String {
+        i18n::translate(self.locale, id, args)
+    }
+
+    pub fn tr_plural(&self, id: &str, count: i64, args: &[(&str, &str)]) -> String {
+        i18n::translate_plural(self.locale, id, count, args)
+    }
+
+    /// Translates the message `id` and adds a remark of the channel's personality to it.
+    pub fn answer(&self, id: &str, args: &[(&str, &str)], is_positive: bool) -> String {
+        self.answered_with(id);
+        self.decorate(&self.tr(id, args), is_positive)
+    }
+
+    /// Like `answer` for a name that could not be found, hinting at `suggestion`
+    /// as the name that was probably meant.
+    pub fn answer_not_found(&self, id: &str, args: &[(&str, &str)], suggestion: Option<String>) -> String {
+        self.answered_with(id);
+
+        let mut text = self.tr(id, args);
+
+        if let Some(suggestion) = suggestion {
+            text = format!("{} {}", text, self.tr("did-you-mean", &[("name", suggestion.as_str())]));
+        }
+
+        self.decorate(&text, false)
+    }
+
+    /// Notes that the command answered with the message `id`. `answer` does
+    /// this by itself, answers composed of several messages have to do it.
+    pub fn answered_with(&self, id: &str) {
+        self.answered.borrow_mut().push(id.to_owned());
+    }
+
+    pub fn answered(&self) -> Vec<String> {
+        self.answered.borrow().clone()
+    }
+
+    /// Adds a remark of the channel's personality to an already translated text.
+    pub fn decorate(&self, text: &str, is_positive: bool) -> String {
+        self.personalities.decorate(&self.personality, self.locale, text, is_positive)
+    }
+}
+
+pub fn get_command_line(whole_input: &str) -> Option<String> {
+    if whole_input.starts_with(COMMAND_TOKEN) {
+        let token_len = COMMAND_TOKEN.len();
+        return Some(whole_input[token_len..].to_owned())
+    }
+
+    None
+}
+
+pub fn get_command(whole_input_sans_command_token: &str) -> Option<String> {
+    let parts: Vec<_> = whole_input_sans_command_token.split_whitespace().collect();
+
+    if parts.len() > 0 {
+        return Some(parts[0].to_owned())
+    } else {
+        None
+    }
+}
+
+pub fn get_command_parameters(whole_input_sans_command_token: &str) -> Vec<String> {
+    let parts: Vec<_> = whole_input_sans_command_token.split_whitespace().collect();
+
+    if get_command(whole_input_sans_command_token).is_some() {
+        return parts[1..].iter().cloned().map(String::from).collect()
+    } else {
+        Vec::new()
+    }
+}
+
+pub fn get_command_from_input(whole_input: &str) -> Option<String> {
+    if whole_input.starts_with(COMMAND_TOKEN) {
+        let token_len = COMMAND_TOKEN.len();
+        let command_part = &whole_input[token_len..];
+
+        return get_command(command_part)
+    }
+
+    None
+}
+
+/// Turns Slack's message markup back into plain text: `&amp;`, `&lt;` and
+/// `&gt;` become `&`, `<` and `>`, and links like `<http://x.de|x.de>` their URL.
+pub fn unescape_slack(text: &str) -> String {
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('<') {
+        plain.push_str(&rest[..start]);
+
+        match rest[start..].find('>') {
+            Some(length) => {
+                let link = &rest[start + 1..start + length];
+                plain.push_str(link.split('|').next().unwrap_or(""));
+                rest = &rest[start + length + 1..];
+            },
+            None => {
+                plain.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+
+    plain.push_str(rest);
+
+    plain.replace("&lt;", "<").replace("&gt;", ">").replace("&amp;", "&")
+}
+
+/// Parses parameters like `key=value`. A parameter without `=` continues the
+/// value of the previous one, so `address=Hauptstraße 1` is a single value.
+pub fn parse_assignments(parameters: &[&str]) -> Vec<(String, String)> {
+    let mut assignments: Vec<(String, String)> = Vec::new();
+
+    for parameter in parameters {
+        let mut parts = parameter.splitn(2, '=');
+        let key = parts.next().unwrap_or("");
+
+        match parts.next() {
+            Some(value) => assignments.push((key.to_lowercase(), value.to_owned())),
+            None => match assignments.last_mut() {
+                Some(&mut (_, ref mut value)) => {
+                    value.push(' ');
+                    value.push_str(parameter);
+                },
+                None => assignments.push((key.to_lowercase(), String::new()))
+            }
+        }
+    }
+
+    assignments
+}
+
+pub fn get_command_implementation<'a>(command_name: &str, command_implementations: &'a HashSet<Command>) -> Option<&'a Command> {
+    for command_implementation in command_implementations {
+        if command_implementation.matches(command_name) {
+            return Some(command_implementation);
+        }
+    }
+
+    None
 }
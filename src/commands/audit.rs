@@ -0,0 +1,57 @@
+use audit;
+use command::{CommandHandler, Context};
+use poll;
+use render;
+
+pub struct ShowAuditLog;
+
+impl CommandHandler for ShowAuditLog {
+    fn name(&self) -> &'static str {
+        "audit"
+    }
+
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if args.len() < 1 {
+            return false;
+        }
+
+        let poll_name = args[0];
+
+        if let Some(channel_id) = context.channel.as_ref() {
+            if !context.is_admin() {
+                context.transport.send_message(channel_id.as_str(), context.answer("audit-admins-only", &[], false).as_str());
+                return true;
+            }
+
+            let poll = match poll::find_poll_by_name(context.repo, poll_name) {
+                Some(poll) => poll,
+                None => {
+                    let suggestion = poll::suggest_poll_name(context.repo, poll_name);
+                    context.transport.send_message(channel_id.as_str(), context.answer_not_found("poll-not-found-by-name", &[("poll", poll_name)], suggestion).as_str());
+                    return true;
+                }
+            };
+
+            let entries: Vec<_> = audit::find_poll_history(context.repo, &poll)
+                .into_iter()
+                .map(|entry| {
+                    let voter = entry.voter_id.and_then(|voter_id| context.repo.find_voter_by_id(voter_id));
+                    (entry, voter)
+                })
+                .collect();
+
+            let message = if entries.is_empty() {
+                context.answer("audit-empty", &[("poll", poll.name.as_str())], false)
+            } else {
+                let header = context.tr("audit-header", &[("poll", poll.name.as_str())]);
+                render::audit_log(context.locale, &header, &poll, &entries)
+            };
+
+            println!("{}", message);
+
+            context.transport.send_message(channel_id.as_str(), message.as_str());
+        }
+
+        true
+    }
+}
@@ -0,0 +1,77 @@
+use command::{CommandHandler, Context};
+use export::{self, ExportFormat};
+use models::Poll;
+use poll;
+use voter;
+
+pub struct Export;
+
+impl CommandHandler for Export {
+    fn name(&self) -> &'static str {
+        "export"
+    }
+
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if args.len() < 2 {
+            return false;
+        }
+
+        let selection = args[0];
+        let format_name = args[1].to_lowercase();
+
+        if let Some(channel_id) = context.channel.as_ref() {
+            let format = match ExportFormat::from_str(&format_name) {
+                Some(format) => format,
+                None => {
+                    context.transport.send_message(channel_id.as_str(), context.answer("export-format-invalid", &[("format", format_name.as_str())], false).as_str());
+                    return true;
+                }
+            };
+
+            let polls = match export::find_polls(context.repo, selection) {
+                Some(polls) => polls,
+                None => {
+                    let suggestion = poll::suggest_poll_name(context.repo, selection);
+                    context.transport.send_message(channel_id.as_str(), context.answer_not_found("poll-not-found-by-name", &[("poll", selection)], suggestion).as_str());
+                    return true;
+                }
+            };
+
+            // Nobody gets results through the export they could not see otherwise
+            let polls: Vec<Poll> = polls.into_iter().filter(|poll| can_see_results(context, poll)).collect();
+
+            if polls.is_empty() {
+                context.transport.send_message(channel_id.as_str(), context.answer("export-nothing", &[], false).as_str());
+                return true;
+            }
+
+            let file_name = export::file_name(if selection == "all" { "all" } else { polls[0].name.as_str() }, format);
+            let content = export::export(context.repo, &polls, format);
+
+            let message_formatted = if context.transport.upload_file(channel_id.as_str(), &file_name, &content) {
+                context.answer("export-uploaded", &[("file", file_name.as_str())], true)
+            } else {
+                context.answer("export-failed", &[("file", file_name.as_str())], false)
+            };
+
+            let message = message_formatted.as_str();
+            println!("{}", message);
+
+            context.transport.send_message(channel_id.as_str(), message);
+        }
+
+        true
+    }
+}
+
+fn can_see_results(context: &Context, poll: &Poll) -> bool {
+    if context.can_manage(poll) {
+        return true;
+    }
+
+    let has_voted = context.user.as_ref()
+        .and_then(|user| voter::find_voter_by_user(context.repo, user))
+        .map_or(false, |voter| !context.repo.find_votes_by_poll_and_voter(poll, voter.id).is_empty());
+
+    poll::can_see_results(poll, has_voted)
+}
@@ -0,0 +1,18 @@
+use command::{CommandHandler, Context};
+
+pub struct Help;
+
+impl CommandHandler for Help {
+    fn name(&self) -> &'static str {
+        "help"
+    }
+
+    #[allow(unused_variables)]
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if let Some(channel_id) = context.channel.as_ref() {
+            context.transport.send_message(channel_id.as_str(), context.answer("help", &[], false).as_str());
+        }
+
+        true
+    }
+}
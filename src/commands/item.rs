@@ -0,0 +1,215 @@
+use audit::AuditScope;
+use command::{CommandHandler, Context, parse_assignments};
+use import;
+use item;
+use models::{ItemFilter, ItemOption, OptionError, Pagination};
+use render;
+
+pub struct NewItem;
+
+impl CommandHandler for NewItem {
+    fn name(&self) -> &'static str {
+        "new_item"
+    }
+
+    fn audit_scope<'p>(&self, _parameters: &[&'p str]) -> AuditScope<'p> {
+        AuditScope::Global
+    }
+
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if args.len() < 1 {
+            return false;
+        }
+
+        let item_name = args[0];
+
+        if let Some(channel_id) = context.channel.as_ref() {
+            let created_item = item::create_item(context.repo, item_name);
+
+            let message_formatted = if created_item {
+                context.answer("item-created", &[("item", item_name)], true)
+            } else {
+                context.answer("item-exists", &[("item", item_name)], false)
+            };
+
+            let message = message_formatted.as_str();
+            println!("{}", message);
+
+            context.transport.send_message(channel_id.as_str(), message);
+        }
+
+        true
+    }
+}
+
+pub struct ListItems;
+
+impl CommandHandler for ListItems {
+    fn name(&self) -> &'static str {
+        "list_items"
+    }
+
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if let Some(channel_id) = context.channel.as_ref() {
+            let mut filter = ItemFilter::default();
+            let mut pagination = Pagination::default();
+
+            for (key, value) in parse_assignments(&args) {
+                if !pagination.add(&key, &value) && !filter.add(&key, &value) {
+                    context.transport.send_message(channel_id.as_str(), context.answer("item-filter-invalid", &[("filter", key.as_str()), ("value", value.as_str())], false).as_str());
+                    return true;
+                }
+            }
+
+            let (results, total) = context.repo.find_items_page(&filter, &pagination);
+
+            println!("Displaying {} of {} items", results.len(), total);
+
+            if results.is_empty() {
+                context.transport.send_message(channel_id.as_str(), context.answer("item-list-empty", &[], false).as_str());
+                return true;
+            }
+
+            let header = render::list_header(context.locale, "item-list-header", &pagination, results.len(), total);
+            let message = render::item_list(context.locale, &header, &results, pagination.offset());
+
+            context.transport.send_message(channel_id.as_str(), message.as_str());
+        }
+
+        true
+    }
+}
+
+pub struct SetItemOption;
+
+impl CommandHandler for SetItemOption {
+    fn name(&self) -> &'static str {
+        "item_set"
+    }
+
+    fn audit_scope<'p>(&self, _parameters: &[&'p str]) -> AuditScope<'p> {
+        AuditScope::Global
+    }
+
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if args.len() < 2 {
+            return false;
+        }
+
+        let item_name = args[0];
+
+        if let Some(channel_id) = context.channel.as_ref() {
+            let item_option = item::find_item_by_name(context.repo, item_name);
+
+            if !item_option.is_some() {
+                context.transport.send_message(channel_id.as_str(), not_found(context, item_name).as_str());
+                return true;
+            }
+
+            let item = item_option.unwrap();
+
+            for (key, value) in parse_assignments(&args[1..]) {
+                let (key, value) = (key.as_str(), value.as_str());
+
+                let message_formatted = match ItemOption::parse(key, value) {
+                    Ok(option) => {
+                        if item::set_option(context.repo, &item, &option) {
+                            context.answer("item-option-set", &[("item", item.name.as_str()), ("option", option.key()), ("value", option.value().as_str())], true)
+                        } else {
+                            context.answer("item-option-not-set", &[("item", item.name.as_str()), ("option", key)], false)
+                        }
+                    },
+                    Err(OptionError::UnknownOption) => context.answer("item-option-unknown", &[("option", key)], false),
+                    Err(OptionError::InvalidValue) => context.answer("item-option-invalid", &[("option", key), ("value", value)], false)
+                };
+
+                let message = message_formatted.as_str();
+                println!("{}", message);
+
+                context.transport.send_message(channel_id.as_str(), message);
+            }
+        }
+
+        true
+    }
+}
+
+pub struct AddItemAlias;
+
+impl CommandHandler for AddItemAlias {
+    fn name(&self) -> &'static str {
+        "item_alias"
+    }
+
+    fn audit_scope<'p>(&self, _parameters: &[&'p str]) -> AuditScope<'p> {
+        AuditScope::Global
+    }
+
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if args.len() < 2 {
+            return false;
+        }
+
+        let item_name = args[0];
+        let alias = args[1..].join(" ");
+
+        if let Some(channel_id) = context.channel.as_ref() {
+            let item_option = item::find_item_by_name(context.repo, item_name);
+
+            if !item_option.is_some() {
+                context.transport.send_message(channel_id.as_str(), not_found(context, item_name).as_str());
+                return true;
+            }
+
+            let item = item_option.unwrap();
+
+            let message_formatted = if item::add_alias(context.repo, &item, &alias) {
+                context.answer("item-alias-added", &[("item", item.name.as_str()), ("alias", alias.as_str())], true)
+            } else {
+                context.answer("item-alias-exists", &[("alias", alias.as_str())], false)
+            };
+
+            let message = message_formatted.as_str();
+            println!("{}", message);
+
+            context.transport.send_message(channel_id.as_str(), message);
+        }
+
+        true
+    }
+}
+
+fn not_found(context: &Context, item_name: &str) -> String {
+    context.answer_not_found("item-not-found-by-name", &[("item", item_name)], item::suggest_item_name(context.repo, item_name))
+}
+
+pub struct ImportItems;
+
+impl CommandHandler for ImportItems {
+    fn name(&self) -> &'static str {
+        "import_items"
+    }
+
+    fn audit_scope<'p>(&self, _parameters: &[&'p str]) -> AuditScope<'p> {
+        AuditScope::Global
+    }
+
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if args.len() < 1 {
+            return false;
+        }
+
+        if let Some(channel_id) = context.channel.as_ref() {
+            let report = import::import_items(context.repo, &context.command_text());
+            context.answered_with("import-report");
+
+            let message_formatted = context.decorate(&render::import_report(context.locale, &report), report.invalid.is_empty());
+            let message = message_formatted.as_str();
+            println!("{}", message);
+
+            context.transport.send_message(channel_id.as_str(), message);
+        }
+
+        true
+    }
+}
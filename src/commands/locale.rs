@@ -0,0 +1,72 @@
+use audit::AuditScope;
+use command::{CommandHandler, Context};
+use i18n::{self, Locale};
+use models::SettingScope;
+
+pub struct SetLocale;
+
+impl CommandHandler for SetLocale {
+    fn name(&self) -> &'static str {
+        "locale"
+    }
+
+    fn audit_scope<'p>(&self, _parameters: &[&'p str]) -> AuditScope<'p> {
+        AuditScope::Global
+    }
+
+    /// `!locale <de|en> [user|channel|workspace]`, the scope defaults to the calling user.
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if args.len() < 1 {
+            return false;
+        }
+
+        let locale_name = args[0];
+        let scope_name = if args.len() > 1 { args[1] } else { "user" };
+
+        if let Some(channel_id) = context.channel.as_ref() {
+            let locale_option = Locale::from_str(locale_name);
+
+            if !locale_option.is_some() {
+                context.transport.send_message(channel_id.as_str(), context.answer("locale-unknown", &[("locale", locale_name)], false).as_str());
+                return true;
+            }
+
+            let locale = locale_option.unwrap();
+
+            let user_id = context.user.as_ref().and_then(|user| user.id.as_ref()).map(String::as_str);
+
+            let scope_option = match scope_name {
+                "user" => user_id.map(|user_id| (SettingScope::User, user_id)),
+                "channel" => Some((SettingScope::Channel, channel_id.as_str())),
+                "workspace" => Some((SettingScope::Workspace, i18n::WORKSPACE_SCOPE_ID)),
+                _ => None
+            };
+
+            if !scope_option.is_some() {
+                context.transport.send_message(channel_id.as_str(), context.answer("locale-scope-unknown", &[("scope", scope_name)], false).as_str());
+                return true;
+            }
+
+            let (scope, scope_id) = scope_option.unwrap();
+
+            // Everyone may choose their own language, but not that of the others
+            if scope != SettingScope::User && !context.is_admin() {
+                context.transport.send_message(channel_id.as_str(), context.answer("locale-admins-only", &[], false).as_str());
+                return true;
+            }
+
+            context.repo.set_setting(scope, scope_id, i18n::LOCALE_SETTING, locale.as_str());
+
+            // Answer in the new language right away
+            context.locale = locale;
+
+            let scope_description = context.tr(&format!("locale-scope-{}", scope_name), &[]);
+            let message = context.answer("locale-set", &[("scope", scope_description.as_str())], true);
+            println!("{}", message);
+
+            context.transport.send_message(channel_id.as_str(), message.as_str());
+        }
+
+        true
+    }
+}
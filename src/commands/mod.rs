@@ -0,0 +1,58 @@
+use command::Command;
+
+use std::collections::HashSet;
+
+mod audit;
+mod export;
+mod help;
+mod item;
+mod locale;
+mod personality;
+mod poll;
+mod stats;
+mod suggest;
+mod vote;
+mod voter;
+
+pub use self::audit::ShowAuditLog;
+pub use self::export::Export;
+pub use self::help::Help;
+pub use self::item::{AddItemAlias, ImportItems, ListItems, NewItem, SetItemOption};
+pub use self::locale::SetLocale;
+pub use self::personality::SetPersonality;
+pub use self::poll::{ConcludePoll, InviteVoters, ListPolls, NewPoll, SetPollOption, ShowPoll, ShowPollResults, StartPoll};
+pub use self::stats::ShowStats;
+pub use self::suggest::{PickItem, SuggestItems};
+pub use self::vote::{NewProposal, Vote};
+pub use self::voter::NewVoter;
+
+/// All commands the bot understands.
+pub fn create_commands() -> HashSet<Command> {
+    let mut commands: HashSet<Command> = HashSet::new();
+    commands.insert(Command::new(Box::new(NewPoll)));
+    commands.insert(Command::new(Box::new(StartPoll)));
+    commands.insert(Command::new(Box::new(ConcludePoll)));
+    commands.insert(Command::new(Box::new(SetPollOption)));
+    commands.insert(Command::new(Box::new(InviteVoters)));
+    commands.insert(Command::new(Box::new(ListPolls)));
+    commands.insert(Command::new(Box::new(ListItems)));
+    commands.insert(Command::new(Box::new(NewVoter)));
+    commands.insert(Command::new(Box::new(NewItem)));
+    commands.insert(Command::new(Box::new(SetItemOption)));
+    commands.insert(Command::new(Box::new(AddItemAlias)));
+    commands.insert(Command::new(Box::new(ImportItems)));
+    commands.insert(Command::new(Box::new(NewProposal)));
+    commands.insert(Command::new(Box::new(Vote)));
+    commands.insert(Command::new(Box::new(ShowPoll)));
+    commands.insert(Command::new(Box::new(ShowPollResults)));
+    commands.insert(Command::new(Box::new(SetLocale)));
+    commands.insert(Command::new(Box::new(SetPersonality)));
+    commands.insert(Command::new(Box::new(PickItem)));
+    commands.insert(Command::new(Box::new(SuggestItems)));
+    commands.insert(Command::new(Box::new(ShowStats)));
+    commands.insert(Command::new(Box::new(Export)));
+    commands.insert(Command::new(Box::new(ShowAuditLog)));
+    commands.insert(Command::new(Box::new(Help)));
+
+    commands
+}
@@ -0,0 +1,67 @@
+use audit::AuditScope;
+use command::{CommandHandler, Context};
+use i18n;
+use models::SettingScope;
+use personality::PERSONALITY_SETTING;
+
+pub struct SetPersonality;
+
+impl CommandHandler for SetPersonality {
+    fn name(&self) -> &'static str {
+        "personality"
+    }
+
+    // Without parameters the personalities are only listed
+    fn audit_scope<'p>(&self, parameters: &[&'p str]) -> AuditScope<'p> {
+        if parameters.is_empty() { AuditScope::None } else { AuditScope::Global }
+    }
+
+    /// `!personality [<name> [channel|workspace]]`, without a name the available
+    /// personalities are listed. The scope defaults to the current channel.
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if let Some(channel_id) = context.channel.as_ref() {
+            let names = context.personalities.names().join(", ");
+
+            if args.len() < 1 {
+                let message = context.tr("personality-list", &[("personalities", names.as_str()), ("personality", context.personality.as_str())]);
+                context.transport.send_message(channel_id.as_str(), message.as_str());
+                return true;
+            }
+
+            let personality_name = args[0];
+            let scope_name = if args.len() > 1 { args[1] } else { "channel" };
+
+            if !context.personalities.exists(personality_name) {
+                context.transport.send_message(channel_id.as_str(), context.answer("personality-unknown", &[("personality", personality_name), ("personalities", names.as_str())], false).as_str());
+                return true;
+            }
+
+            let (scope, scope_id) = match scope_name {
+                "channel" => (SettingScope::Channel, channel_id.as_str()),
+                "workspace" => (SettingScope::Workspace, i18n::WORKSPACE_SCOPE_ID),
+                _ => {
+                    context.transport.send_message(channel_id.as_str(), context.answer("personality-scope-unknown", &[("scope", scope_name)], false).as_str());
+                    return true;
+                }
+            };
+
+            if scope == SettingScope::Workspace && !context.is_admin() {
+                context.transport.send_message(channel_id.as_str(), context.answer("personality-admins-only", &[], false).as_str());
+                return true;
+            }
+
+            context.repo.set_setting(scope, scope_id, PERSONALITY_SETTING, personality_name);
+
+            if scope == SettingScope::Channel || !context.repo.find_setting(SettingScope::Channel, channel_id, PERSONALITY_SETTING).is_some() {
+                context.personality = personality_name.to_owned();
+            }
+
+            let message = context.answer(&format!("personality-set-{}", scope_name), &[("personality", personality_name)], true);
+            println!("{}", message);
+
+            context.transport.send_message(channel_id.as_str(), message.as_str());
+        }
+
+        true
+    }
+}
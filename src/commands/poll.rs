@@ -0,0 +1,445 @@
+use audit::AuditScope;
+use command::{CommandHandler, Context, parse_assignments};
+use models::{Pagination, PollFilter, PollOption, OptionError, PollVisibility};
+use poll;
+use render;
+use tally;
+use voter;
+
+use models::Poll;
+
+pub struct NewPoll;
+
+impl CommandHandler for NewPoll {
+    fn name(&self) -> &'static str {
+        "new_poll"
+    }
+
+    fn audit_scope<'p>(&self, parameters: &[&'p str]) -> AuditScope<'p> {
+        AuditScope::first_poll(parameters)
+    }
+
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if args.len() < 1 {
+            return false;
+        }
+
+        let poll_name = args[0];
+
+        if let Some(channel_id) = context.channel.as_ref() {
+            let created_poll = poll::create_poll(context.repo, poll_name, context.user_id(), Some(channel_id.as_str()));
+
+            let message_formatted = if created_poll {
+                context.answer("poll-created", &[("poll", poll_name)], true)
+            } else {
+                context.answer("poll-not-created", &[("poll", poll_name)], false)
+            };
+
+            let message = message_formatted.as_str();
+            println!("{}", message);
+
+            context.transport.send_message(channel_id.as_str(), message);
+        }
+
+        true
+    }
+}
+
+pub struct StartPoll;
+
+impl CommandHandler for StartPoll {
+    fn name(&self) -> &'static str {
+        "start_poll"
+    }
+
+    fn audit_scope<'p>(&self, parameters: &[&'p str]) -> AuditScope<'p> {
+        AuditScope::first_poll(parameters)
+    }
+
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if args.len() < 1 {
+            return false;
+        }
+
+        let poll_name = args[0];
+
+        if let Some(channel_id) = context.channel.as_ref() {
+            let poll_option = poll::find_poll_by_name(context.repo, poll_name);
+
+            if !poll_option.is_some() {
+                context.transport.send_message(channel_id.as_str(), not_found(context, "poll-not-found", poll_name).as_str());
+            } else {
+                let poll = poll_option.unwrap();
+                let poll_name = poll.name.as_str();
+
+                if poll::can_start_poll(context.repo, poll_name) {
+                    let started_poll = poll::start_poll(context.repo, poll_name);
+
+                    let message_formatted = if started_poll {
+                        let started = format!("{} {}", context.tr("poll-started", &[("poll", poll_name)]), eligibility(context, &poll));
+                        context.answered_with("poll-started");
+                        context.decorate(&started, true)
+                    } else {
+                        context.answer("poll-not-started", &[("poll", poll_name)], false)
+                    };
+
+                    let message = message_formatted.as_str();
+                    println!("{}", message);
+
+                    context.transport.send_message(channel_id.as_str(), message);
+                } else {
+                    context.transport.send_message(channel_id.as_str(), context.answer("poll-already-started", &[], false).as_str());
+                }
+            }
+        }
+
+        true
+    }
+}
+
+pub struct ConcludePoll;
+
+impl CommandHandler for ConcludePoll {
+    fn name(&self) -> &'static str {
+        "conclude_poll"
+    }
+
+    fn audit_scope<'p>(&self, parameters: &[&'p str]) -> AuditScope<'p> {
+        AuditScope::first_poll(parameters)
+    }
+
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if args.len() < 1 {
+            return false;
+        }
+
+        let poll_option = poll::find_poll_by_name(context.repo, args[0]);
+        let poll_name = poll_option.as_ref().map_or(args[0], |poll| poll.name.as_str());
+
+        if let Some(channel_id) = context.channel.as_ref() {
+            if !poll_option.is_some() {
+                context.transport.send_message(channel_id.as_str(), not_found(context, "poll-cannot-be-concluded", poll_name).as_str());
+            } else if poll::can_conclude_poll(context.repo, poll_name) {
+                conclude_poll(context, channel_id.as_str(), poll_name);
+            } else {
+                context.transport.send_message(channel_id.as_str(), context.answer("poll-cannot-be-concluded", &[("poll", poll_name)], false).as_str());
+            }
+        }
+
+        true
+    }
+}
+
+/// Concludes the poll and announces its winner. Polls that conclude by
+/// themselves go through here as well.
+pub fn conclude_poll(context: &Context, channel_id: &str, poll_name: &str) {
+    let concluded_poll = poll::conclude_poll(context.repo, poll_name);
+
+    let message_formatted = if concluded_poll {
+        context.answer("poll-concluded", &[("poll", poll_name)], true)
+    } else {
+        context.answer("poll-not-concluded", &[("poll", poll_name)], false)
+    };
+
+    let message = message_formatted.as_str();
+    println!("{}", message);
+
+    context.transport.send_message(channel_id, message);
+
+    if concluded_poll {
+        if let Some(poll) = context.repo.find_poll_by_name(poll_name) {
+            let tally = tally::tally_poll(context.repo, context.transport, &poll);
+            context.transport.send_message(channel_id, render::conclusion(context.locale, poll_name, &tally).as_str());
+        }
+    }
+}
+
+pub struct SetPollOption;
+
+impl CommandHandler for SetPollOption {
+    fn name(&self) -> &'static str {
+        "poll_set"
+    }
+
+    fn audit_scope<'p>(&self, parameters: &[&'p str]) -> AuditScope<'p> {
+        AuditScope::first_poll(parameters)
+    }
+
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if args.len() < 2 {
+            return false;
+        }
+
+        let poll_name = args[0];
+
+        if let Some(channel_id) = context.channel.as_ref() {
+            let poll_option = poll::find_poll_by_name(context.repo, poll_name);
+
+            if !poll_option.is_some() {
+                context.transport.send_message(channel_id.as_str(), not_found(context, "poll-not-found-by-name", poll_name).as_str());
+                return true;
+            }
+
+            let poll = poll_option.unwrap();
+            let poll_name = poll.name.as_str();
+
+            if !context.can_manage(&poll) {
+                context.transport.send_message(channel_id.as_str(), context.answer("poll-not-managed", &[("poll", poll_name)], false).as_str());
+                return true;
+            }
+
+            if !poll::can_change_options(&poll) {
+                context.transport.send_message(channel_id.as_str(), context.answer("poll-options-locked", &[("poll", poll_name)], false).as_str());
+                return true;
+            }
+
+            for (key, value) in parse_assignments(&args[1..]) {
+                let (key, value) = (key.as_str(), value.as_str());
+
+                let message_formatted = match PollOption::parse(key, value) {
+                    Ok(option) => {
+                        if poll::set_option(context.repo, &poll, &option) {
+                            context.answer("poll-option-set", &[("poll", poll_name), ("option", option.key()), ("value", option.value().as_str())], true)
+                        } else {
+                            context.answer("poll-option-not-set", &[("poll", poll_name), ("option", key)], false)
+                        }
+                    },
+                    Err(OptionError::UnknownOption) => context.answer("poll-option-unknown", &[("option", key)], false),
+                    Err(OptionError::InvalidValue) => context.answer("poll-option-invalid", &[("option", key), ("value", value)], false)
+                };
+
+                let message = message_formatted.as_str();
+                println!("{}", message);
+
+                context.transport.send_message(channel_id.as_str(), message);
+            }
+        }
+
+        true
+    }
+}
+
+pub struct InviteVoters;
+
+impl CommandHandler for InviteVoters {
+    fn name(&self) -> &'static str {
+        "invite"
+    }
+
+    fn audit_scope<'p>(&self, parameters: &[&'p str]) -> AuditScope<'p> {
+        AuditScope::first_poll(parameters)
+    }
+
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if args.len() < 2 {
+            return false;
+        }
+
+        let poll_name = args[0];
+
+        if let Some(channel_id) = context.channel.as_ref() {
+            let poll_option = poll::find_poll_by_name(context.repo, poll_name);
+
+            if !poll_option.is_some() {
+                context.transport.send_message(channel_id.as_str(), not_found(context, "poll-not-found-by-name", poll_name).as_str());
+                return true;
+            }
+
+            let poll = poll_option.unwrap();
+            let poll_name = poll.name.as_str();
+
+            if !context.can_manage(&poll) {
+                context.transport.send_message(channel_id.as_str(), context.answer("poll-not-managed", &[("poll", poll_name)], false).as_str());
+                return true;
+            }
+
+            for mention in args[1..].iter() {
+                let voter_option = voter::parse_mention(mention)
+                    .and_then(|slack_id| context.repo.find_voter_by_slack_id(slack_id));
+
+                let message_formatted = match voter_option {
+                    Some(voter) => {
+                        if context.repo.insert_poll_participant(poll.id, voter.id) {
+                            context.answer("invite-added", &[("poll", poll_name), ("name", voter.name.as_str())], true)
+                        } else {
+                            context.answer("invite-exists", &[("poll", poll_name), ("name", voter.name.as_str())], false)
+                        }
+                    },
+                    None => context.answer("invite-unknown-voter", &[("name", mention)], false)
+                };
+
+                let message = message_formatted.as_str();
+                println!("{}", message);
+
+                context.transport.send_message(channel_id.as_str(), message);
+            }
+        }
+
+        true
+    }
+}
+
+pub struct ListPolls;
+
+impl CommandHandler for ListPolls {
+    fn name(&self) -> &'static str {
+        "list_polls"
+    }
+
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if let Some(channel_id) = context.channel.as_ref() {
+            let mut filter = PollFilter::default();
+            let mut pagination = Pagination::default();
+
+            for (key, value) in parse_assignments(&args) {
+                if !pagination.add(&key, &value) && !filter.add(&key, &value) {
+                    context.transport.send_message(channel_id.as_str(), context.answer("poll-filter-invalid", &[("filter", key.as_str()), ("value", value.as_str())], false).as_str());
+                    return true;
+                }
+            }
+
+            let (results, total) = context.repo.find_polls_page(&filter, &pagination);
+
+            println!("Displaying {} of {} polls", results.len(), total);
+
+            if results.is_empty() {
+                context.transport.send_message(channel_id.as_str(), context.answer("poll-list-empty", &[], false).as_str());
+                return true;
+            }
+
+            let header = render::list_header(context.locale, "poll-list-header", &pagination, results.len(), total);
+            let message = render::poll_list(context.locale, &header, &results, pagination.offset());
+
+            context.transport.send_rich_message(channel_id.as_str(), &message);
+        }
+
+        true
+    }
+}
+
+pub struct ShowPoll;
+
+impl CommandHandler for ShowPoll {
+    fn name(&self) -> &'static str {
+        "poll"
+    }
+
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if args.len() < 1 {
+            return false;
+        }
+
+        let poll_name = args[0];
+
+        if let Some(channel_id) = context.channel.as_ref() {
+            let poll = match poll::find_poll_by_name(context.repo, poll_name) {
+                Some(poll) => poll,
+                None => {
+                    context.transport.send_message(channel_id.as_str(), not_found(context, "poll-not-found-by-name", poll_name).as_str());
+                    return true;
+                }
+            };
+
+            let tally = tally::tally_poll(context.repo, context.transport, &poll);
+
+            let has_voted = context.user.as_ref()
+                .and_then(|user| voter::find_voter_by_user(context.repo, user))
+                .map(|voter| tally.has_voted(voter.id));
+
+            let mut message = render::poll_details(context.locale, &poll, &eligibility(context, &poll));
+
+            if context.can_manage(&poll) || poll::can_see_results(&poll, has_voted.unwrap_or(false)) {
+                let header = context.tr_plural("poll-details-proposals", tally.proposals.len() as i64, &[]);
+                message = format!("{}{}", message, render::standings(context.locale, &header, &tally));
+            } else {
+                // Only the names in the order they were proposed, so the ranking stays hidden
+                let items: Vec<String> = context.repo.find_proposals_by_poll(&poll)
+                    .iter()
+                    .filter_map(|proposal| context.repo.find_item_by_id(proposal.item_id))
+                    .map(|item| item.name)
+                    .collect();
+
+                message = format!("{}{} {}\n{}\n", message,
+                                  context.tr_plural("poll-details-proposals", items.len() as i64, &[]),
+                                  items.join(", "),
+                                  context.tr_plural("poll-details-voters", tally.voters_voted as i64, &[]));
+            }
+
+            match has_voted {
+                Some(true) => message = format!("{}{}\n", message, context.tr("poll-details-you-voted", &[])),
+                Some(false) => message = format!("{}{}\n", message, context.tr("poll-details-you-did-not-vote", &[])),
+                None => ()
+            }
+
+            println!("{}", message);
+
+            context.transport.send_message(channel_id.as_str(), message.as_str());
+        }
+
+        true
+    }
+}
+
+pub struct ShowPollResults;
+
+impl CommandHandler for ShowPollResults {
+    fn name(&self) -> &'static str {
+        "show_poll_results"
+    }
+
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if args.len() < 1 {
+            return false;
+        }
+
+        let poll_name = args[0];
+
+        if let Some(channel_id) = context.channel.as_ref() {
+            let poll_option = poll::find_poll_by_name(context.repo, poll_name);
+
+            if !poll_option.is_some() {
+                context.transport.send_message(channel_id.as_str(), not_found(context, "results-poll-not-found", poll_name).as_str());
+                return true;
+            }
+
+            let poll = poll_option.unwrap();
+            let poll_name = poll.name.as_str();
+
+            let tally = tally::tally_poll(context.repo, context.transport, &poll);
+
+            let has_voted = context.user.as_ref()
+                .and_then(|user| voter::find_voter_by_user(context.repo, user))
+                .map_or(false, |voter| tally.has_voted(voter.id));
+
+            if !context.can_manage(&poll) && !poll::can_see_results(&poll, has_voted) {
+                let message_id = match PollVisibility::from_str(poll.visibility.as_str()) {
+                    Some(PollVisibility::AfterVote) => "results-hidden-until-vote",
+                    _ => "results-hidden-until-conclusion"
+                };
+
+                context.transport.send_message(channel_id.as_str(), context.answer(message_id, &[("poll", poll_name)], false).as_str());
+                return true;
+            }
+
+            let mut header = context.tr("results-header", &[("poll", poll_name)]);
+
+            if poll.anonymous {
+                header = format!("{}\n{}", header, context.tr("results-anonymous", &[]));
+            }
+
+            let message = render::poll_results(context.locale, &header, &tally, !poll.anonymous);
+
+            context.transport.send_rich_message(channel_id.as_str(), &message);
+        }
+
+        true
+    }
+}
+
+fn eligibility(context: &Context, poll: &Poll) -> String {
+    render::eligibility(context.locale, poll, &context.repo.find_participants_by_poll(poll))
+}
+
+fn not_found(context: &Context, message_id: &str, poll_name: &str) -> String {
+    context.answer_not_found(message_id, &[("poll", poll_name)], poll::suggest_poll_name(context.repo, poll_name))
+}
@@ -0,0 +1,81 @@
+use command::{CommandHandler, Context};
+use item;
+use poll;
+use render;
+use stats;
+
+const NUM_TOP_ITEMS: usize = 3;
+
+pub struct ShowStats;
+
+impl CommandHandler for ShowStats {
+    fn name(&self) -> &'static str {
+        "stats"
+    }
+
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if let Some(channel_id) = context.channel.as_ref() {
+            let message_formatted = match args.first().map(|kind| *kind) {
+                None => {
+                    let overview = stats::overview(context.repo, NUM_TOP_ITEMS);
+
+                    if overview.concluded_polls == 0 {
+                        context.answer("stats-no-polls", &[], false)
+                    } else {
+                        render::stats_overview(context.locale, &overview)
+                    }
+                },
+                Some("items") => {
+                    let item_stats = context.repo.find_item_stats();
+
+                    if item_stats.is_empty() {
+                        context.answer("stats-no-polls", &[], false)
+                    } else {
+                        render::item_stats(context.locale, &context.tr("stats-items-header", &[]), &item_stats)
+                    }
+                },
+                Some("voters") => {
+                    // Anonymous polls are left out of the voter statistics
+                    let concluded_polls = context.repo.find_polls()
+                        .iter()
+                        .filter(|poll| poll::is_concluded(poll) && !poll.anonymous)
+                        .count();
+
+                    if concluded_polls == 0 {
+                        context.answer("stats-no-polls", &[], false)
+                    } else {
+                        render::voter_stats(context.locale, &context.tr("stats-voters-header", &[]), &context.repo.find_voter_stats(), concluded_polls)
+                    }
+                },
+                Some("item") if args.len() > 1 => {
+                    let item_name = args[1..].join(" ");
+
+                    match item::find_item_by_name(context.repo, &item_name) {
+                        Some(item) => {
+                            let points = context.repo.find_item_trend(&item);
+
+                            if points.is_empty() {
+                                context.answer("stats-item-never-proposed", &[("item", item.name.as_str())], false)
+                            } else {
+                                let header = context.tr("stats-trend-header", &[("item", item.name.as_str())]);
+                                render::item_trend(context.locale, &header, &points, stats::trend(&points))
+                            }
+                        },
+                        None => {
+                            let suggestion = item::suggest_item_name(context.repo, &item_name);
+                            context.answer_not_found("item-not-found-by-name", &[("item", item_name.as_str())], suggestion)
+                        }
+                    }
+                },
+                _ => context.answer("stats-unknown", &[], false)
+            };
+
+            let message = message_formatted.as_str();
+            println!("{}", message);
+
+            context.transport.send_message(channel_id.as_str(), message);
+        }
+
+        true
+    }
+}
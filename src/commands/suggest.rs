@@ -0,0 +1,134 @@
+use rand::Rng;
+
+use audit::AuditScope;
+use command::{CommandHandler, Context, parse_assignments};
+use models::ItemFilter;
+use poll;
+use render;
+use suggest;
+use vote;
+
+const DEFAULT_NUM_SUGGESTIONS: usize = 3;
+
+pub struct PickItem;
+
+impl CommandHandler for PickItem {
+    fn name(&self) -> &'static str {
+        "pick"
+    }
+
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if let Some(channel_id) = context.channel.as_ref() {
+            let mut filter = ItemFilter::default();
+
+            for (key, value) in parse_assignments(&args) {
+                if !filter.add(&key, &value) {
+                    context.transport.send_message(channel_id.as_str(), context.answer("item-filter-invalid", &[("filter", key.as_str()), ("value", value.as_str())], false).as_str());
+                    return true;
+                }
+            }
+
+            let items = context.repo.find_items_matching(&filter);
+            let picked = context.rng().choose(&items).map(|item| item.name.clone());
+
+            let message_formatted = match picked {
+                Some(item_name) => context.answer("pick-result", &[("item", item_name.as_str())], true),
+                None => context.answer("pick-nothing", &[], false)
+            };
+
+            let message = message_formatted.as_str();
+            println!("{}", message);
+
+            context.transport.send_message(channel_id.as_str(), message);
+        }
+
+        true
+    }
+}
+
+pub struct SuggestItems;
+
+impl CommandHandler for SuggestItems {
+    fn name(&self) -> &'static str {
+        "suggest"
+    }
+
+    // Only suggestions for a poll are proposed and change something
+    fn audit_scope<'p>(&self, parameters: &[&'p str]) -> AuditScope<'p> {
+        parameters.iter().cloned()
+            .find(|parameter| parameter.to_lowercase().starts_with("poll="))
+            .map_or(AuditScope::None, |parameter| AuditScope::Poll(&parameter["poll=".len()..]))
+    }
+
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if let Some(channel_id) = context.channel.as_ref() {
+            let mut filter = ItemFilter::default();
+            let mut count = DEFAULT_NUM_SUGGESTIONS;
+            let mut poll_name = None;
+
+            for (key, value) in parse_assignments(&args) {
+                let valid = match key.as_str() {
+                    "count" => match value.parse::<usize>() {
+                        Ok(value) if value > 0 => {
+                            count = value;
+                            true
+                        },
+                        _ => false
+                    },
+                    "poll" => {
+                        poll_name = Some(value.clone());
+                        true
+                    },
+                    _ => filter.add(&key, &value)
+                };
+
+                if !valid {
+                    context.transport.send_message(channel_id.as_str(), context.answer("item-filter-invalid", &[("filter", key.as_str()), ("value", value.as_str())], false).as_str());
+                    return true;
+                }
+            }
+
+            // Suggestions for a poll are proposed right away
+            let poll_option = match poll_name {
+                Some(ref poll_name) => match poll::find_poll_by_name(context.repo, poll_name) {
+                    Some(ref poll) if poll::is_concluded(poll) => {
+                        context.transport.send_message(channel_id.as_str(), context.answer("suggest-poll-concluded", &[("poll", poll.name.as_str())], false).as_str());
+                        return true;
+                    },
+                    Some(poll) => Some(poll),
+                    None => {
+                        let suggestion = poll::suggest_poll_name(context.repo, poll_name);
+                        context.transport.send_message(channel_id.as_str(), context.answer_not_found("poll-not-found-by-name", &[("poll", poll_name.as_str())], suggestion).as_str());
+                        return true;
+                    }
+                },
+                None => None
+            };
+
+            let items = context.repo.find_items_matching(&filter);
+            let suggestions = suggest::suggest(context.repo, items, count, &mut *context.rng());
+
+            if suggestions.is_empty() {
+                context.transport.send_message(channel_id.as_str(), context.answer("pick-nothing", &[], false).as_str());
+                return true;
+            }
+
+            let header = context.tr_plural("suggest-header", suggestions.len() as i64, &[]);
+            let mut message = render::suggestions(context.locale, &header, &suggestions);
+
+            if let Some(poll) = poll_option {
+                for suggestion in suggestions.iter() {
+                    vote::create_proposal(context.repo, &poll, &suggestion.item);
+                }
+
+                message = format!("{}{}", message, context.answer("suggest-proposed", &[("poll", poll.name.as_str())], true));
+            }
+
+            println!("{}", message);
+
+            context.transport.send_message(channel_id.as_str(), message.as_str());
+        }
+
+        true
+    }
+}
@@ -0,0 +1,217 @@
+use audit::AuditScope;
+use command::{CommandHandler, Context};
+use item;
+use models::{AutoConclude, PollEligibility};
+use poll;
+use tally;
+use vote;
+use voter;
+
+use super::poll::conclude_poll;
+
+pub struct NewProposal;
+
+impl CommandHandler for NewProposal {
+    fn name(&self) -> &'static str {
+        "new_proposal"
+    }
+
+    fn audit_scope<'p>(&self, parameters: &[&'p str]) -> AuditScope<'p> {
+        AuditScope::first_poll(parameters)
+    }
+
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if args.len() < 2 {
+            return false;
+        }
+
+        let poll_name = args[0];
+        let item_name = args[1];
+
+        if let Some(channel_id) = context.channel.as_ref() {
+            let poll_option = poll::find_poll_by_name(context.repo, poll_name);
+            let item_option = item::find_item_by_name(context.repo, item_name);
+
+            if poll_option.is_none() {
+                let suggestion = poll::suggest_poll_name(context.repo, poll_name);
+                context.transport.send_message(channel_id.as_str(), context.answer_not_found("poll-not-found-by-name", &[("poll", poll_name)], suggestion).as_str());
+                return true;
+            }
+
+            if item_option.is_none() {
+                let suggestion = item::suggest_item_name(context.repo, item_name);
+                context.transport.send_message(channel_id.as_str(), context.answer_not_found("item-not-found-by-name", &[("item", item_name)], suggestion).as_str());
+                return true;
+            }
+
+            let poll = poll_option.unwrap();
+            let item = item_option.unwrap();
+            let (poll_name, item_name) = (poll.name.as_str(), item.name.as_str());
+
+            let created_proposal = vote::create_proposal(context.repo, &poll, &item);
+            let message_formatted = if created_proposal {
+                context.answer("proposal-created", &[("poll", poll_name), ("item", item_name)], true)
+            } else {
+                context.answer("proposal-exists", &[("poll", poll_name), ("item", item_name)], false)
+            };
+
+            let message = message_formatted.as_str();
+            println!("{}", message);
+
+            context.transport.send_message(channel_id.as_str(), message);
+        }
+
+        true
+    }
+}
+
+pub struct Vote;
+
+impl CommandHandler for Vote {
+    fn name(&self) -> &'static str {
+        "vote"
+    }
+
+    fn audit_scope<'p>(&self, parameters: &[&'p str]) -> AuditScope<'p> {
+        AuditScope::first_poll(parameters)
+    }
+
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if args.len() < 3 {
+            return false;
+        }
+
+        let poll_name = args[0];
+        let item_name = args[1];
+
+        if let Some(channel_id) = context.channel.as_ref() {
+            let poll_option = poll::find_poll_by_name(context.repo, poll_name);
+            let item_option = item::find_item_by_name(context.repo, item_name);
+
+            if !poll_option.is_some() || !item_option.is_some() {
+                let suggestion = if poll_option.is_none() {
+                    poll::suggest_poll_name(context.repo, poll_name)
+                } else {
+                    item::suggest_item_name(context.repo, item_name)
+                };
+
+                context.transport.send_message(channel_id.as_str(), context.answer_not_found("vote-poll-or-item-not-found", &[("poll", poll_name), ("item", item_name)], suggestion).as_str());
+                return true;
+            }
+
+            let poll = poll_option.unwrap();
+            let item = item_option.unwrap();
+            let (poll_name, item_name) = (poll.name.as_str(), item.name.as_str());
+
+            // Votes posted to a channel would give away the secret
+            if poll.anonymous && !context.is_direct_message() {
+                context.transport.send_message(channel_id.as_str(), context.answer("vote-direct-message-only", &[("poll", poll_name)], false).as_str());
+                return true;
+            }
+
+            let weight = match vote::parse_weight(args[2]) {
+                Some(weight) => vote::effective_weight(&poll, weight),
+                None => {
+                    let max_weight = vote::MAX_WEIGHT.to_string();
+                    context.transport.send_message(channel_id.as_str(), context.answer("vote-weight-invalid", &[("weight", args[2]), ("max", max_weight.as_str())], false).as_str());
+                    return true;
+                }
+            };
+
+            let user = context.user.as_ref().unwrap();
+
+            // Open polls register everyone on their first vote
+            if PollEligibility::from_str(poll.eligibility.as_str()) == Some(PollEligibility::Open) {
+                if let (Some(user_id), Some(user_name)) = (user.id.as_ref(), user.name.as_ref()) {
+                    voter::create_voter(context.repo, user_id, user_name);
+                }
+            }
+
+            let voter_option = voter::find_voter_by_user(context.repo, user);
+
+            if !voter_option.is_some() {
+                let voter_name = context.user.as_ref().unwrap().name.as_ref().unwrap().as_str();
+                context.transport.send_message(channel_id.as_str(), context.answer("vote-unknown-voter", &[("name", voter_name)], false).as_str());
+                return true;
+            }
+
+            let voter = voter_option.unwrap();
+
+            if !vote::is_eligible(context.repo, context.transport, &poll, &voter) {
+                context.transport.send_message(channel_id.as_str(), context.answer("vote-not-eligible", &[("poll", poll_name)], false).as_str());
+                return true;
+            }
+
+            let proposal_option = vote::find_or_create_proposal(context.repo, &poll, &item);
+
+            if !proposal_option.is_some() {
+                context.transport.send_message(channel_id.as_str(), context.answer("vote-proposal-not-created", &[("poll", poll_name), ("item", item_name)], false).as_str());
+                return true;
+            }
+
+            let proposal = proposal_option.unwrap();
+
+            let set_vote = vote::set_vote(context.repo, context.transport, voter.id, proposal.id, weight);
+
+            let message_formatted = if set_vote {
+                let counted = context.tr("vote-counted", &[("poll", poll_name), ("item", item_name), ("weight", weight.to_string().as_str())]);
+                let budget = vote::remaining_budget(context.repo, &poll, voter.id, None);
+
+                context.answered_with("vote-counted");
+                context.decorate(&with_budget(context, counted, &budget), true)
+            } else {
+                let budget = vote::remaining_budget(context.repo, &poll, voter.id, Some(proposal.id));
+
+                if vote::can_vote_for_proposal(context.repo, context.transport, &proposal, &voter) && !budget.allows(weight) {
+                    let over_budget = context.tr("vote-over-budget", &[("poll", poll_name), ("item", item_name)]);
+                    context.answered_with("vote-over-budget");
+                    context.decorate(&with_budget(context, over_budget, &budget), false)
+                } else {
+                    context.answer("vote-rejected", &[("poll", poll_name), ("item", item_name)], false)
+                }
+            };
+
+            let message = message_formatted.as_str();
+
+            if !poll.anonymous {
+                println!("{}", message);
+            }
+
+            context.transport.send_message(channel_id.as_str(), message);
+
+            if set_vote {
+                let tally = tally::tally_poll(context.repo, context.transport, &poll);
+
+                if let Some(reason) = poll::should_auto_conclude(context.repo, context.transport, &poll, &tally) {
+                    // Announce in the poll's channel, votes of anonymous polls come in as direct messages
+                    let poll_channel_id = poll.channel_id.clone().unwrap_or(channel_id.clone());
+
+                    let reason_id = match reason {
+                        AutoConclude::Majority => "poll-auto-concluded-majority",
+                        _ => "poll-auto-concluded-all-voted"
+                    };
+
+                    context.transport.send_message(poll_channel_id.as_str(), context.tr(reason_id, &[("poll", poll_name)]).as_str());
+                    conclude_poll(context, poll_channel_id.as_str(), poll_name);
+                }
+            }
+        }
+
+        true
+    }
+}
+
+// Appends what is left of a limited budget to `message`
+fn with_budget(context: &Context, message: String, budget: &vote::Budget) -> String {
+    let mut message = message;
+
+    if let Some(points) = budget.points {
+        message = format!("{} {}", message, context.tr_plural("vote-budget-points", points as i64, &[]));
+    }
+
+    if let Some(approvals) = budget.approvals {
+        message = format!("{} {}", message, context.tr_plural("vote-budget-approvals", approvals as i64, &[]));
+    }
+
+    message
+}
@@ -0,0 +1,44 @@
+use audit::AuditScope;
+use command::{CommandHandler, Context};
+use voter;
+
+pub struct NewVoter;
+
+impl CommandHandler for NewVoter {
+    fn name(&self) -> &'static str {
+        "new_voter"
+    }
+
+    fn audit_scope<'p>(&self, _parameters: &[&'p str]) -> AuditScope<'p> {
+        AuditScope::Global
+    }
+
+    #[allow(unused_variables)]
+    fn handle(&self, context: &mut Context, args: Vec<&str>) -> bool {
+        if !context.user.is_some() {
+            println!("Error: Cannot create voter.");
+            return true;
+        }
+
+        let user = context.user.as_ref().unwrap();
+        let user_name = user.name.as_ref().unwrap().as_str();
+        let user_id = user.id.as_ref().unwrap().as_str();
+
+        if let Some(channel_id) = context.channel.as_ref() {
+            let created_voter = voter::create_voter(context.repo, user_id, user_name);
+
+            let message_formatted = if created_voter {
+                context.answer("voter-created", &[("name", user_name), ("id", user_id)], true)
+            } else {
+                context.answer("voter-exists", &[("id", user_id), ("name", user_name)], false)
+            };
+
+            let message = message_formatted.as_str();
+            println!("{}", message);
+
+            context.transport.send_message(channel_id.as_str(), message);
+        }
+
+        true
+    }
+}
@@ -0,0 +1,192 @@
+use serde_json::{self, Value};
+
+use models::*;
+use poll;
+use repository::PollRepository;
+
+/// Columns of the CSV export, one row per vote. Proposals without votes get
+/// a row with empty voter columns.
+pub const CSV_HEADER: &'static [&'static str] = &[
+    "poll", "status", "anonymous", "started_at", "concluded_at",
+    "visibility", "budget", "max_approvals", "eligibility", "quorum", "auto_conclude", "creator_slack_id", "channel_id",
+    "item", "category", "address", "price_level", "menu_url", "vegetarian", "vegan", "tags",
+    "voter", "voter_slack_id", "weight"
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Json
+}
+
+impl ExportFormat {
+    pub fn from_str(s: &str) -> Option<ExportFormat> {
+        match s {
+            "csv" => Some(ExportFormat::Csv),
+            "json" => Some(ExportFormat::Json),
+            _ => None
+        }
+    }
+
+    pub fn extension(&self) -> &str {
+        match self {
+            &ExportFormat::Csv => "csv",
+            &ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// The polls selected by `all` or by the name of a single poll.
+pub fn find_polls(repo: &PollRepository, selection: &str) -> Option<Vec<Poll>> {
+    if selection == "all" {
+        Some(repo.find_polls())
+    } else {
+        poll::find_poll_by_name(repo, selection).map(|poll| vec![poll])
+    }
+}
+
+/// E.g. `lunch-mittag.csv` or `lunch-all.json`. Poll names are chosen by
+/// users, so everything but letters, digits, `-` and `_` is replaced.
+pub fn file_name(selection: &str, format: ExportFormat) -> String {
+    let selection: String = selection.chars()
+        .map(|c| if ((c as u32) < 128 && c.is_alphanumeric()) || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+
+    format!("lunch-{}.{}", selection, format.extension())
+}
+
+/// Serializes the polls with their proposals, items, votes and voters.
+/// Voters of anonymous polls are left out.
+pub fn export(repo: &PollRepository, polls: &[Poll], format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Csv => to_csv(repo, polls),
+        ExportFormat::Json => to_json(repo, polls)
+    }
+}
+
+fn to_csv(repo: &PollRepository, polls: &[Poll]) -> String {
+    let mut csv = format!("{}\n", CSV_HEADER.join(","));
+
+    for poll in polls.iter() {
+        for (item, votes) in repo.find_poll_results(poll) {
+            let columns = vec![
+                poll.name.clone(),
+                poll.status.clone(),
+                poll.anonymous.to_string(),
+                poll.started_at.clone().unwrap_or_default(),
+                poll.concluded_at.clone().unwrap_or_default(),
+                poll.visibility.clone(),
+                optional_number(poll.budget),
+                optional_number(poll.max_approvals),
+                poll.eligibility.clone(),
+                optional_number(poll.quorum),
+                poll.auto_conclude.clone(),
+                poll.creator_slack_id.clone().unwrap_or_default(),
+                poll.channel_id.clone().unwrap_or_default(),
+                item.name.clone(),
+                item.category.clone().unwrap_or_default(),
+                item.address.clone().unwrap_or_default(),
+                optional_number(item.price_level),
+                item.menu_url.clone().unwrap_or_default(),
+                item.vegetarian.to_string(),
+                item.vegan.to_string(),
+                item.tag_list().join(",")
+            ];
+
+            if votes.is_empty() {
+                csv = format!("{}{}\n", csv, csv_row(&columns, &["", "", ""]));
+            }
+
+            for (vote, voter) in votes {
+                let weight = vote.weight.to_string();
+
+                let row = if poll.anonymous {
+                    csv_row(&columns, &["", "", weight.as_str()])
+                } else {
+                    csv_row(&columns, &[voter.name.as_str(), voter.slack_id.as_ref().map_or("", String::as_str), weight.as_str()])
+                };
+
+                csv = format!("{}{}\n", csv, row);
+            }
+        }
+    }
+
+    csv
+}
+
+fn optional_number(number: Option<i32>) -> String {
+    number.map(|number| number.to_string()).unwrap_or_default()
+}
+
+fn csv_row(columns: &[String], vote_columns: &[&str]) -> String {
+    columns.iter()
+        .map(String::as_str)
+        .chain(vote_columns.iter().map(|column| *column))
+        .map(csv_field)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+// Quotes fields containing separators, quotes or line breaks
+fn csv_field(field: &str) -> String {
+    if field.contains(',') || field.contains('"') || field.contains('\n') || field.contains('\r') {
+        format!("\"{}\"", field.replace("\"", "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+fn to_json(repo: &PollRepository, polls: &[Poll]) -> String {
+    let polls_json: Vec<Value> = polls.iter()
+        .map(|poll| {
+            let proposals: Vec<Value> = repo.find_poll_results(poll)
+                .into_iter()
+                .map(|(item, votes)| json!({
+                    "item": item_json(&item),
+                    "votes": votes.iter().map(|&(ref vote, ref voter)| {
+                        if poll.anonymous {
+                            json!({ "weight": vote.weight })
+                        } else {
+                            json!({
+                                "voter": { "name": voter.name, "slack_id": voter.slack_id },
+                                "weight": vote.weight
+                            })
+                        }
+                    }).collect::<Vec<_>>()
+                }))
+                .collect();
+
+            json!({
+                "name": poll.name,
+                "status": poll.status,
+                "anonymous": poll.anonymous,
+                "visibility": poll.visibility,
+                "budget": poll.budget,
+                "max_approvals": poll.max_approvals,
+                "eligibility": poll.eligibility,
+                "quorum": poll.quorum,
+                "auto_conclude": poll.auto_conclude,
+                "creator_slack_id": poll.creator_slack_id,
+                "channel_id": poll.channel_id,
+                "started_at": poll.started_at,
+                "concluded_at": poll.concluded_at,
+                "proposals": proposals
+            })
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&json!({ "polls": polls_json })).expect("Cannot serialize export.")
+}
+
+fn item_json(item: &Item) -> Value {
+    json!({
+        "name": item.name,
+        "category": item.category,
+        "address": item.address,
+        "price_level": item.price_level,
+        "menu_url": item.menu_url,
+        "vegetarian": item.vegetarian,
+        "vegan": item.vegan,
+        "tags": item.tag_list()
+    })
+}
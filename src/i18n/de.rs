@@ -0,0 +1,173 @@
+pub static MESSAGES: &'static [(&'static str, &'static str)] = &[
+    ("unknown-command", "Ich kenne den Befehl '{command}' nicht."),
+    ("insufficient-parameters", "Unzureichende Anzahl an Parametern für den Befehl '{command}'."),
+    ("did-you-mean", "Meintest du '{name}'?"),
+    ("help", "Ich kann dir jetzt gerade nicht helfen :confused:. Frag' doch einfach eine kompetente Person?"),
+
+    ("locale-set", "Ab jetzt spreche ich Deutsch mit {scope}."),
+    ("locale-unknown", "Die Sprache '{locale}' spreche ich nicht. Ich kann: de, en."),
+    ("locale-scope-unknown", "Ich kenne den Bereich '{scope}' nicht. Möglich sind: user, channel, workspace."),
+    ("locale-admins-only", "Nur Admins dürfen die Sprache eines Kanals oder aller ändern!"),
+    ("locale-scope-user", "dir"),
+    ("locale-scope-channel", "diesem Kanal"),
+    ("locale-scope-workspace", "allen"),
+
+    ("personality-list", "Verfügbare Persönlichkeiten: {personalities}. Hier bin ich gerade '{personality}'."),
+    ("personality-unknown", "Die Persönlichkeit '{personality}' kenne ich nicht. Verfügbar sind: {personalities}."),
+    ("personality-scope-unknown", "Ich kenne den Bereich '{scope}' nicht. Möglich sind: channel, workspace."),
+    ("personality-admins-only", "Nur Admins dürfen die Persönlichkeit für alle Kanäle ändern!"),
+    ("personality-set-channel", "In diesem Kanal bin ich ab jetzt '{personality}'."),
+    ("personality-set-workspace", "Ich bin ab jetzt überall '{personality}', wo nichts anderes eingestellt ist."),
+
+    ("poll-created", "Umfrage '{poll}' angelegt."),
+    ("poll-not-created", "Ich kann die Umfrage '{poll}' nicht anlegen!"),
+    ("poll-started", "Umfrage '{poll}' gestartet."),
+    ("poll-not-started", "Ich kann die Umfrage '{poll}' nicht starten!"),
+    ("poll-already-started", "Ich kann keine Umfrage erneut starten, sofern sie schon einmal gestartet worden ist."),
+    ("poll-concluded", "Umfrage beendet '{poll}'."),
+    ("poll-not-concluded", "Ich kann die Umfrage '{poll}' nicht beenden!"),
+    ("poll-cannot-be-concluded", "Ich kann die Umfrage '{poll}' nicht beenden, weil sie entweder nicht existiert oder bereits beendet worden ist."),
+    ("poll-auto-concluded-all-voted", "Alle haben bei der Umfrage '{poll}' abgestimmt."),
+    ("poll-auto-concluded-majority", "Ein Vorschlag der Umfrage '{poll}' hat die absolute Mehrheit."),
+    ("poll-not-found", "Ich kann keine Umfrage '{poll}' finden!"),
+    ("poll-not-found-by-name", "Ich kann keine Umfrage namens '{poll}' finden!"),
+    ("poll-list-header", "Umfragen {first} bis {last} von {total} (Seite {page} von {pages}):"),
+    ("poll-list-empty", "Ich finde keine passenden Umfragen!"),
+    ("poll-filter-invalid", "Ich kann nicht nach '{filter}={value}' filtern. Möglich sind: status, search, page, limit, sort."),
+    ("poll-not-managed", "Nur wer die Umfrage '{poll}' angelegt hat, darf sie ändern!"),
+    ("poll-options-locked", "Die Einstellungen der Umfrage '{poll}' kann ich nur ändern, solange sie noch nicht gestartet ist!"),
+    ("poll-option-set", "Einstellung '{option}' der Umfrage '{poll}' ist jetzt '{value}'."),
+    ("poll-option-not-set", "Ich kann die Einstellung '{option}' der Umfrage '{poll}' nicht ändern!"),
+    ("poll-option-unknown", "Ich kenne keine Umfrage-Einstellung '{option}'. Möglich sind: anonymous, visibility, budget, max_approvals, eligibility, quorum, auto_conclude."),
+    ("poll-option-invalid", "'{value}' ist kein gültiger Wert für '{option}'."),
+
+    ("poll-details-header", "Umfrage '{poll}': {status}"),
+    ("poll-details-creator", "Angelegt von {creator}"),
+    ("poll-details-channel", "Kanal: {channel}"),
+    ("poll-details-method-approval", "Abstimmung mit + und -"),
+    ("poll-details-method-budget.one", "Abstimmung mit {count} Punkt pro Wähler"),
+    ("poll-details-method-budget.other", "Abstimmung mit {count} Punkten pro Wähler"),
+    ("poll-details-max-approvals.one", "höchstens {count} Zustimmung"),
+    ("poll-details-max-approvals.other", "höchstens {count} Zustimmungen"),
+    ("poll-details-anonymous", "geheim"),
+    ("poll-details-quorum.one", "Mindestbeteiligung: {count} Wähler"),
+    ("poll-details-quorum.other", "Mindestbeteiligung: {count} Wähler"),
+    ("poll-details-started", "Gestartet: {time} UTC"),
+    ("poll-details-concluded", "Beendet: {time} UTC"),
+    ("poll-details-proposals.one", "{count} Vorschlag:"),
+    ("poll-details-proposals.other", "{count} Vorschläge:"),
+    ("poll-details-voters.one", "{count} Wähler hat abgestimmt."),
+    ("poll-details-voters.other", "{count} Wähler haben abgestimmt."),
+    ("poll-details-you-voted", "Du hast abgestimmt."),
+    ("poll-details-you-did-not-vote", "Du hast noch nicht abgestimmt."),
+    ("eligibility-open", "Abstimmen dürfen alle."),
+    ("eligibility-registered", "Abstimmen dürfen alle registrierten Wähler."),
+    ("eligibility-channel", "Abstimmen dürfen die Mitglieder von {channel}."),
+    ("eligibility-invited.one", "Abstimmen darf nur der eingeladene Wähler {voters}."),
+    ("eligibility-invited.other", "Abstimmen dürfen nur die {count} eingeladenen Wähler {voters}."),
+
+    ("invite-added", "'{name}' darf jetzt bei der Umfrage '{poll}' abstimmen."),
+    ("invite-exists", "'{name}' ist zur Umfrage '{poll}' bereits eingeladen!"),
+    ("invite-unknown-voter", "Ich kenne keinen Wähler {name}. Erwähne registrierte Wähler mit @name!"),
+
+    ("status-in-progress", "läuft"),
+    ("status-stopped", "gestoppt"),
+    ("status-concluded", "beendet"),
+    ("status-aborted", "abgebrochen"),
+
+    ("results-total", "Summe"),
+    ("results-votes", "Stimmen"),
+    ("results-approval", "Zustimmung"),
+    ("results-turnout.one", "Beteiligung: {voted} von {count} Wähler ({turnout}%)"),
+    ("results-turnout.other", "Beteiligung: {voted} von {count} Wählern ({turnout}%)"),
+    ("conclusion-winner", ":trophy: '{item}' gewinnt die Umfrage '{poll}' mit {score} ({approval}% Zustimmung)."),
+    ("conclusion-no-quorum", "Die Umfrage '{poll}' hat keinen Gewinner, weil nur {voted} statt mindestens {quorum} Wählern abgestimmt haben."),
+    ("conclusion-no-winner", "Die Umfrage '{poll}' hat keinen eindeutigen Gewinner."),
+
+    ("results-poll-not-found", "Ich kann keine Umfrageergebnisse für '{poll}' anzeigen, weil es die Umfrage nicht gibt!"),
+    ("results-header", "Hier die Umfrageergebnisse für {poll}:"),
+    ("results-hidden-until-vote", "Die Ergebnisse der Umfrage '{poll}' zeige ich dir erst, wenn du abgestimmt hast."),
+    ("results-hidden-until-conclusion", "Die Ergebnisse der Umfrage '{poll}' zeige ich erst, wenn sie beendet ist."),
+    ("results-anonymous", "Geheime Umfrage: Einzelne Stimmen zeige ich nicht an."),
+
+    ("item-created", "Ort '{item}' angelegt."),
+    ("item-exists", "Ort '{item}' gibt es bereits!"),
+    ("item-not-found-by-name", "Ich kann keinen Ort namens '{item}' finden!"),
+    ("item-list-header", "Orte {first} bis {last} von {total} (Seite {page} von {pages}):"),
+    ("item-list-empty", "Ich finde keine passenden Orte!"),
+    ("item-filter-invalid", "Ich kann nicht nach '{filter}={value}' filtern. Möglich sind: search, tag, category, vegetarian, vegan, price."),
+    ("item-option-set", "'{option}' von '{item}' ist jetzt '{value}'."),
+    ("item-option-not-set", "Ich kann '{option}' von '{item}' nicht ändern!"),
+    ("item-option-unknown", "Ich kenne keine Angabe '{option}'. Möglich sind: category, address, price, menu, vegetarian, vegan, tags."),
+    ("item-option-invalid", "'{value}' ist kein gültiger Wert für '{option}'."),
+    ("item-alias-added", "'{alias}' ist jetzt ein anderer Name für '{item}'."),
+    ("item-alias-exists", "Einen Ort namens '{alias}' gibt es bereits!"),
+    ("item-vegetarian", "vegetarisch"),
+    ("item-vegan", "vegan"),
+
+    ("pick-result", "Wie wäre es mit '{item}'?"),
+    ("pick-nothing", "Ich finde keinen passenden Ort!"),
+    ("suggest-header.one", "Mein Vorschlag:"),
+    ("suggest-header.other", "Meine {count} Vorschläge:"),
+    ("suggest-wins.one", "{count} Sieg"),
+    ("suggest-wins.other", "{count} Siege"),
+    ("suggest-never-won", "noch nie gewonnen"),
+    ("suggest-last-win.one", "zuletzt vor {count} Tag"),
+    ("suggest-last-win.other", "zuletzt vor {count} Tagen"),
+    ("suggest-downvotes.one", "zuletzt {count} Gegenstimme"),
+    ("suggest-downvotes.other", "zuletzt {count} Gegenstimmen"),
+    ("suggest-poll-concluded", "Die Umfrage '{poll}' ist schon beendet, da schlage ich nichts mehr vor."),
+    ("suggest-proposed", "Die Vorschläge stehen jetzt in der Umfrage '{poll}' zur Wahl."),
+
+    ("stats-header.one", "Statistik über {count} beendete Umfrage:"),
+    ("stats-header.other", "Statistik über {count} beendete Umfragen:"),
+    ("stats-no-polls", "Es gibt noch keine beendeten Umfragen, über die ich etwas sagen könnte."),
+    ("stats-no-winners", "Bisher hat noch kein Ort gewonnen."),
+    ("stats-top-items", "Meiste Siege: {items}"),
+    ("stats-turnout", "Durchschnittliche Beteiligung: {participants} von {voters} Wählern ({turnout}%)"),
+    ("stats-contrarian", "Am häufigsten gegen das Ergebnis: {voter} ({rate}% der Stimmen)"),
+    ("stats-items-header", "Siege pro Ort:"),
+    ("stats-item", "{item}: {wins} von {proposals} Umfragen gewonnen ({rate}%)"),
+    ("stats-voters-header", "Beteiligung pro Wähler:"),
+    ("stats-voter", "{voter}: {polls} von {total} Umfragen ({participation}%), {contrarian} von {votes} Stimmen gegen das Ergebnis"),
+    ("stats-trend-header", "Verlauf von '{item}':"),
+    ("stats-trend-rising", "Tendenz: steigend :chart_with_upwards_trend:"),
+    ("stats-trend-falling", "Tendenz: fallend :chart_with_downwards_trend:"),
+    ("stats-trend-steady", "Tendenz: gleichbleibend"),
+    ("stats-unknown", "Ich kenne `!stats`, `!stats items`, `!stats voters` und `!stats item <Name>`."),
+    ("stats-item-never-proposed", "'{item}' stand noch in keiner beendeten Umfrage zur Wahl."),
+
+    ("export-format-invalid", "Ich kann nur als csv oder json exportieren, nicht als '{format}'."),
+    ("export-nothing", "Es gibt nichts, was ich dir exportieren könnte."),
+    ("export-uploaded", "Hier ist der Export '{file}'."),
+    ("export-failed", "Ich kann den Export '{file}' nicht hochladen!"),
+
+    ("import-report", "Import fertig: {created} angelegt, {skipped} schon vorhanden, {invalid} ungültig."),
+    ("import-invalid-entries", "Ungültige Einträge: {entries}."),
+
+    ("audit-admins-only", "Nur Admins dürfen ins Protokoll schauen!"),
+    ("audit-header", "Protokoll von '{poll}':"),
+    ("audit-empty", "Für '{poll}' wurde noch nichts protokolliert."),
+    ("audit-anonymous", "jemand"),
+    ("audit-unknown-user", "unbekannt"),
+
+    ("voter-created", "Neuer Wähler '{name}' angelegt ({id})."),
+    ("voter-exists", "Wähler '{id}' ('{name}') ist bereits registriert!"),
+
+    ("proposal-created", "Vorschlag '{poll}' bei '{item}' angelegt."),
+    ("proposal-exists", "Der Vorschlag für '{poll}' bei '{item}' existiert bereits!"),
+
+    ("vote-counted", "Stimme gezählt für '{poll}' bei '{item}' mit Gewichtung {weight}."),
+    ("vote-poll-or-item-not-found", "Ich kann die Umfrage '{poll}' oder den Ort '{item}' nicht finden!"),
+    ("vote-proposal-not-created", "Ich kann für die Umfrage '{poll}' mit Ort '{item}' keinen Vorschlag anlegen!"),
+    ("vote-unknown-voter", "Ich kenne keinen Wähler '{name}'. Die Stimme ist nicht gezählt worden."),
+    ("vote-rejected", "Ich kann die Stimme für '{poll}' bei '{item}' nicht akzeptieren!"),
+    ("vote-over-budget", "Die Stimme für '{poll}' bei '{item}' übersteigt dein Budget!"),
+    ("vote-budget-points.one", "Du hast noch {count} Punkt übrig."),
+    ("vote-budget-points.other", "Du hast noch {count} Punkte übrig."),
+    ("vote-budget-approvals.one", "Du kannst noch für {count} Ort stimmen."),
+    ("vote-budget-approvals.other", "Du kannst noch für {count} Orte stimmen."),
+    ("vote-not-eligible", "Du darfst bei der Umfrage '{poll}' nicht abstimmen!"),
+    ("vote-direct-message-only", "Die Umfrage '{poll}' ist geheim. Schick mir deine Stimme bitte als Direktnachricht!"),
+    ("vote-weight-invalid", "Ich verstehe die Gewichtung '{weight}' nicht. Nimm `+`, `-` oder eine Zahl von -{max} bis {max} außer 0."),
+];
@@ -0,0 +1,173 @@
+pub static MESSAGES: &'static [(&'static str, &'static str)] = &[
+    ("unknown-command", "I don't know the command '{command}'."),
+    ("insufficient-parameters", "Not enough parameters for the command '{command}'."),
+    ("did-you-mean", "Did you mean '{name}'?"),
+    ("help", "I can't help you right now :confused:. Why don't you ask a competent person?"),
+
+    ("locale-set", "From now on I'll speak English with {scope}."),
+    ("locale-unknown", "I don't speak '{locale}'. I can do: de, en."),
+    ("locale-scope-unknown", "I don't know the scope '{scope}'. Possible are: user, channel, workspace."),
+    ("locale-admins-only", "Only admins may change the language of a channel or everyone!"),
+    ("locale-scope-user", "you"),
+    ("locale-scope-channel", "this channel"),
+    ("locale-scope-workspace", "everyone"),
+
+    ("personality-list", "Available personalities: {personalities}. Here I'm currently '{personality}'."),
+    ("personality-unknown", "I don't know the personality '{personality}'. Available are: {personalities}."),
+    ("personality-scope-unknown", "I don't know the scope '{scope}'. Possible are: channel, workspace."),
+    ("personality-admins-only", "Only admins may change the personality of the whole workspace!"),
+    ("personality-set-channel", "From now on I'm '{personality}' in this channel."),
+    ("personality-set-workspace", "From now on I'm '{personality}' wherever nothing else is configured."),
+
+    ("poll-created", "Created poll '{poll}'."),
+    ("poll-not-created", "I can't create the poll '{poll}'!"),
+    ("poll-started", "Started poll '{poll}'."),
+    ("poll-not-started", "I can't start the poll '{poll}'!"),
+    ("poll-already-started", "I can't start a poll again once it has been started."),
+    ("poll-concluded", "Concluded poll '{poll}'."),
+    ("poll-not-concluded", "I can't conclude the poll '{poll}'!"),
+    ("poll-cannot-be-concluded", "I can't conclude the poll '{poll}' because it either doesn't exist or has already been concluded."),
+    ("poll-auto-concluded-all-voted", "Everyone has voted in the poll '{poll}'."),
+    ("poll-auto-concluded-majority", "A proposal of the poll '{poll}' has an absolute majority."),
+    ("poll-not-found", "I can't find a poll '{poll}'!"),
+    ("poll-not-found-by-name", "I can't find a poll named '{poll}'!"),
+    ("poll-list-header", "Polls {first} to {last} of {total} (page {page} of {pages}):"),
+    ("poll-list-empty", "I can't find any matching polls!"),
+    ("poll-filter-invalid", "I can't filter by '{filter}={value}'. Possible are: status, search, page, limit, sort."),
+    ("poll-not-managed", "Only the creator of the poll '{poll}' may change it!"),
+    ("poll-options-locked", "I can only change the settings of the poll '{poll}' before it is started!"),
+    ("poll-option-set", "Setting '{option}' of the poll '{poll}' is now '{value}'."),
+    ("poll-option-not-set", "I can't change the setting '{option}' of the poll '{poll}'!"),
+    ("poll-option-unknown", "I don't know a poll setting '{option}'. Possible are: anonymous, visibility, budget, max_approvals, eligibility, quorum, auto_conclude."),
+    ("poll-option-invalid", "'{value}' is not a valid value for '{option}'."),
+
+    ("poll-details-header", "Poll '{poll}': {status}"),
+    ("poll-details-creator", "Created by {creator}"),
+    ("poll-details-channel", "Channel: {channel}"),
+    ("poll-details-method-approval", "Voting with + and -"),
+    ("poll-details-method-budget.one", "Voting with {count} point per voter"),
+    ("poll-details-method-budget.other", "Voting with {count} points per voter"),
+    ("poll-details-max-approvals.one", "at most {count} approval"),
+    ("poll-details-max-approvals.other", "at most {count} approvals"),
+    ("poll-details-anonymous", "secret"),
+    ("poll-details-quorum.one", "Quorum: {count} voter"),
+    ("poll-details-quorum.other", "Quorum: {count} voters"),
+    ("poll-details-started", "Started: {time} UTC"),
+    ("poll-details-concluded", "Concluded: {time} UTC"),
+    ("poll-details-proposals.one", "{count} proposal:"),
+    ("poll-details-proposals.other", "{count} proposals:"),
+    ("poll-details-voters.one", "{count} voter has voted."),
+    ("poll-details-voters.other", "{count} voters have voted."),
+    ("poll-details-you-voted", "You have voted."),
+    ("poll-details-you-did-not-vote", "You have not voted yet."),
+    ("eligibility-open", "Everyone may vote."),
+    ("eligibility-registered", "All registered voters may vote."),
+    ("eligibility-channel", "The members of {channel} may vote."),
+    ("eligibility-invited.one", "Only the invited voter {voters} may vote."),
+    ("eligibility-invited.other", "Only the {count} invited voters {voters} may vote."),
+
+    ("invite-added", "'{name}' may now vote in the poll '{poll}'."),
+    ("invite-exists", "'{name}' has already been invited to the poll '{poll}'!"),
+    ("invite-unknown-voter", "I don't know a voter {name}. Mention registered voters with @name!"),
+
+    ("status-in-progress", "in progress"),
+    ("status-stopped", "stopped"),
+    ("status-concluded", "concluded"),
+    ("status-aborted", "aborted"),
+
+    ("results-total", "Total"),
+    ("results-votes", "Votes"),
+    ("results-approval", "Approval"),
+    ("results-turnout.one", "Turnout: {voted} of {count} voter ({turnout}%)"),
+    ("results-turnout.other", "Turnout: {voted} of {count} voters ({turnout}%)"),
+    ("conclusion-winner", ":trophy: '{item}' wins the poll '{poll}' with {score} ({approval}% approval)."),
+    ("conclusion-no-quorum", "The poll '{poll}' has no winner because only {voted} instead of at least {quorum} voters voted."),
+    ("conclusion-no-winner", "The poll '{poll}' has no clear winner."),
+
+    ("results-poll-not-found", "I can't show results for '{poll}' because there is no such poll!"),
+    ("results-header", "Here are the results for {poll}:"),
+    ("results-hidden-until-vote", "I'll show you the results of the poll '{poll}' once you have voted."),
+    ("results-hidden-until-conclusion", "I'll show the results of the poll '{poll}' once it is concluded."),
+    ("results-anonymous", "Secret poll: I don't show individual votes."),
+
+    ("item-created", "Created place '{item}'."),
+    ("item-exists", "The place '{item}' already exists!"),
+    ("item-not-found-by-name", "I can't find a place named '{item}'!"),
+    ("item-list-header", "Places {first} to {last} of {total} (page {page} of {pages}):"),
+    ("item-list-empty", "I can't find any matching places!"),
+    ("item-filter-invalid", "I can't filter by '{filter}={value}'. Possible are: search, tag, category, vegetarian, vegan, price."),
+    ("item-option-set", "'{option}' of '{item}' is now '{value}'."),
+    ("item-option-not-set", "I can't change '{option}' of '{item}'!"),
+    ("item-option-unknown", "I don't know a detail '{option}'. Possible are: category, address, price, menu, vegetarian, vegan, tags."),
+    ("item-option-invalid", "'{value}' is not a valid value for '{option}'."),
+    ("item-alias-added", "'{alias}' is now another name for '{item}'."),
+    ("item-alias-exists", "There already is a place named '{alias}'!"),
+    ("item-vegetarian", "vegetarian"),
+    ("item-vegan", "vegan"),
+
+    ("pick-result", "How about '{item}'?"),
+    ("pick-nothing", "I can't find a matching place!"),
+    ("suggest-header.one", "My suggestion:"),
+    ("suggest-header.other", "My {count} suggestions:"),
+    ("suggest-wins.one", "{count} win"),
+    ("suggest-wins.other", "{count} wins"),
+    ("suggest-never-won", "never won"),
+    ("suggest-last-win.one", "last time {count} day ago"),
+    ("suggest-last-win.other", "last time {count} days ago"),
+    ("suggest-downvotes.one", "{count} recent downvote"),
+    ("suggest-downvotes.other", "{count} recent downvotes"),
+    ("suggest-poll-concluded", "The poll '{poll}' is already concluded, so I won't suggest anything for it."),
+    ("suggest-proposed", "The suggestions are now up for vote in the poll '{poll}'."),
+
+    ("stats-header.one", "Statistics of {count} concluded poll:"),
+    ("stats-header.other", "Statistics of {count} concluded polls:"),
+    ("stats-no-polls", "There are no concluded polls I could tell you something about yet."),
+    ("stats-no-winners", "No place has won yet."),
+    ("stats-top-items", "Most wins: {items}"),
+    ("stats-turnout", "Average turnout: {participants} of {voters} voters ({turnout}%)"),
+    ("stats-contrarian", "Most often against the result: {voter} ({rate}% of their votes)"),
+    ("stats-items-header", "Wins per place:"),
+    ("stats-item", "{item}: won {wins} of {proposals} polls ({rate}%)"),
+    ("stats-voters-header", "Participation per voter:"),
+    ("stats-voter", "{voter}: {polls} of {total} polls ({participation}%), {contrarian} of {votes} votes against the result"),
+    ("stats-trend-header", "History of '{item}':"),
+    ("stats-trend-rising", "Trend: rising :chart_with_upwards_trend:"),
+    ("stats-trend-falling", "Trend: falling :chart_with_downwards_trend:"),
+    ("stats-trend-steady", "Trend: steady"),
+    ("stats-unknown", "I know `!stats`, `!stats items`, `!stats voters` and `!stats item <name>`."),
+    ("stats-item-never-proposed", "'{item}' has not been up for vote in a concluded poll yet."),
+
+    ("export-format-invalid", "I can only export as csv or json, not as '{format}'."),
+    ("export-nothing", "There is nothing I could export for you."),
+    ("export-uploaded", "Here is the export '{file}'."),
+    ("export-failed", "I can't upload the export '{file}'!"),
+
+    ("import-report", "Import done: {created} created, {skipped} already existing, {invalid} invalid."),
+    ("import-invalid-entries", "Invalid entries: {entries}."),
+
+    ("audit-admins-only", "Only admins may look into the audit log!"),
+    ("audit-header", "Audit log of '{poll}':"),
+    ("audit-empty", "Nothing has been recorded for '{poll}' yet."),
+    ("audit-anonymous", "someone"),
+    ("audit-unknown-user", "unknown"),
+
+    ("voter-created", "Registered new voter '{name}' ({id})."),
+    ("voter-exists", "Voter '{id}' ('{name}') is already registered!"),
+
+    ("proposal-created", "Created proposal '{poll}' for '{item}'."),
+    ("proposal-exists", "The proposal for '{poll}' with '{item}' already exists!"),
+
+    ("vote-counted", "Counted vote for '{poll}' with '{item}' and weight {weight}."),
+    ("vote-poll-or-item-not-found", "I can't find the poll '{poll}' or the place '{item}'!"),
+    ("vote-proposal-not-created", "I can't create a proposal for the poll '{poll}' with place '{item}'!"),
+    ("vote-unknown-voter", "I don't know a voter '{name}'. The vote has not been counted."),
+    ("vote-rejected", "I can't accept the vote for '{poll}' with '{item}'!"),
+    ("vote-over-budget", "The vote for '{poll}' with '{item}' exceeds your budget!"),
+    ("vote-budget-points.one", "You have {count} point left."),
+    ("vote-budget-points.other", "You have {count} points left."),
+    ("vote-budget-approvals.one", "You can vote for {count} more place."),
+    ("vote-budget-approvals.other", "You can vote for {count} more places."),
+    ("vote-not-eligible", "You may not vote in the poll '{poll}'!"),
+    ("vote-direct-message-only", "The poll '{poll}' is secret. Please send me your vote as a direct message!"),
+    ("vote-weight-invalid", "I don't understand the weight '{weight}'. Use `+`, `-` or a number from -{max} to {max} other than 0."),
+];
@@ -0,0 +1,110 @@
+use models::SettingScope;
+use repository::PollRepository;
+
+mod de;
+mod en;
+
+pub const DEFAULT_LOCALE: Locale = Locale::De;
+
+/// Name of the setting holding the locale of a workspace, channel or user.
+pub const LOCALE_SETTING: &'static str = "locale";
+
+/// Scope id used for workspace wide settings. The bot only ever serves one workspace.
+pub const WORKSPACE_SCOPE_ID: &'static str = "";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Locale {
+    De,
+    En
+}
+
+impl Locale {
+    pub fn from_str(s: &str) -> Option<Locale> {
+        match s.to_lowercase().as_str() {
+            "de" => Some(Locale::De),
+            "en" => Some(Locale::En),
+            _ => None
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            &Locale::De => "de",
+            &Locale::En => "en",
+        }
+    }
+
+    fn catalog(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            &Locale::De => de::MESSAGES,
+            &Locale::En => en::MESSAGES,
+        }
+    }
+}
+
+/// Looks up the message `id` and fills in the `{name}` placeholders from `args`.
+/// Messages missing in `locale` fall back to the default locale.
+pub fn translate(locale: Locale, id: &str, args: &[(&str, &str)]) -> String {
+    let template = lookup(locale, id)
+        .or_else(|| lookup(DEFAULT_LOCALE, id))
+        .unwrap_or(id);
+
+    format_message(template, args)
+}
+
+/// Like `translate`, but picks the `<id>.one` or `<id>.other` variant depending
+/// on `count`, which is also available as the `{count}` placeholder.
+pub fn translate_plural(locale: Locale, id: &str, count: i64, args: &[(&str, &str)]) -> String {
+    let plural_id = format!("{}.{}", id, plural_category(locale, count));
+    let count_string = count.to_string();
+
+    let mut all_args = vec![("count", count_string.as_str())];
+    all_args.extend_from_slice(args);
+
+    translate(locale, &plural_id, &all_args)
+}
+
+/// Finds the locale to talk in: the user's choice wins over the channel's,
+/// which wins over the workspace's.
+pub fn resolve_locale(repo: &PollRepository, channel_id: Option<&str>, user_id: Option<&str>) -> Locale {
+    let mut candidates = Vec::new();
+
+    if let Some(user_id) = user_id {
+        candidates.push((SettingScope::User, user_id));
+    }
+
+    if let Some(channel_id) = channel_id {
+        candidates.push((SettingScope::Channel, channel_id));
+    }
+
+    candidates.push((SettingScope::Workspace, WORKSPACE_SCOPE_ID));
+
+    candidates.into_iter()
+        .filter_map(|(scope, scope_id)| repo.find_setting(scope, scope_id, LOCALE_SETTING))
+        .filter_map(|locale| Locale::from_str(&locale))
+        .next()
+        .unwrap_or(DEFAULT_LOCALE)
+}
+
+fn lookup(locale: Locale, id: &str) -> Option<&'static str> {
+    locale.catalog()
+        .iter()
+        .find(|&&(message_id, _)| message_id == id)
+        .map(|&(_, template)| template)
+}
+
+// German and English share the same plural rules.
+#[allow(unused_variables)]
+fn plural_category(locale: Locale, count: i64) -> &'static str {
+    if count == 1 { "one" } else { "other" }
+}
+
+fn format_message(template: &str, args: &[(&str, &str)]) -> String {
+    let mut message = template.to_owned();
+
+    for &(name, value) in args {
+        message = message.replace(&format!("{{{}}}", name), value);
+    }
+
+    message
+}
@@ -0,0 +1,513 @@
+use serde_json::{self, Value};
+
+use std::collections::HashMap;
+use std::mem;
+
+use item;
+use models::*;
+use repository::PollRepository;
+
+/// What happened to the entries of an import file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImportReport {
+    pub created: usize,
+    /// Entries that already existed.
+    pub skipped: usize,
+    /// Numbers of the rejected entries, starting at 1.
+    pub invalid: Vec<usize>
+}
+
+/// A place read from an import file. The options are `ItemOption` keys and values.
+#[derive(Clone, Debug)]
+struct ItemRecord {
+    name: String,
+    options: Vec<(String, String)>
+}
+
+#[derive(Clone, Debug)]
+struct VoteRecord {
+    voter_name: Option<String>,
+    voter_slack_id: Option<String>,
+    weight: i32
+}
+
+#[derive(Clone, Debug)]
+struct ProposalRecord {
+    item: ItemRecord,
+    votes: Vec<VoteRecord>
+}
+
+/// The settings of a poll in an export, all but `anonymous`.
+const POLL_OPTION_KEYS: &'static [&'static str] = &["visibility", "budget", "max_approvals", "eligibility", "quorum", "auto_conclude"];
+
+/// A poll as written by `export`. The options are `PollOption` keys and values.
+#[derive(Clone, Debug)]
+struct PollRecord {
+    name: String,
+    status: String,
+    anonymous: bool,
+    options: Vec<(String, String)>,
+    creator_slack_id: Option<String>,
+    channel_id: Option<String>,
+    started_at: Option<String>,
+    concluded_at: Option<String>,
+    proposals: Vec<ProposalRecord>
+}
+
+/// Creates the places listed in `content`, either a JSON array of objects
+/// or CSV with a header row. Besides `name` the columns of `!item_set` are
+/// read, so the CSV export of polls can be imported as places as well.
+pub fn import_items(repo: &PollRepository, content: &str) -> ImportReport {
+    let records = if is_json(content) { items_from_json(content) } else { items_from_csv(content) };
+    let mut report = ImportReport::default();
+
+    for (num, record) in records.into_iter().enumerate() {
+        let (name, options) = match record.and_then(|record| parse_options(&record).map(|options| (record.name, options)).map_err(|_| ())) {
+            Ok(parsed) => parsed,
+            Err(_) => {
+                report.invalid.push(num + 1);
+                continue;
+            }
+        };
+
+        if !item::create_item(repo, &name) {
+            report.skipped += 1;
+            continue;
+        }
+
+        if let Some(item) = item::find_item_by_name(repo, &name) {
+            for option in options.iter() {
+                repo.update_item_option(item.id, option);
+            }
+        }
+
+        report.created += 1;
+    }
+
+    report
+}
+
+/// Recreates the polls of an export with their settings, proposals, places,
+/// voters and votes. Polls that already exist are skipped. Votes of anonymous
+/// polls are dropped, as the export does not contain their voters and every
+/// vote needs one.
+pub fn import_history(repo: &PollRepository, content: &str) -> ImportReport {
+    let records = if is_json(content) { polls_from_json(content) } else { polls_from_csv(content) };
+    let mut report = ImportReport::default();
+
+    for (num, record) in records.into_iter().enumerate() {
+        let (record, options) = match record.and_then(|record| poll_options(&record).map(|options| (record, options)).map_err(|_| ())) {
+            Ok(parsed) => parsed,
+            Err(_) => {
+                report.invalid.push(num + 1);
+                continue;
+            }
+        };
+
+        if repo.find_poll_by_name(&record.name).is_some() {
+            report.skipped += 1;
+            continue;
+        }
+
+        import_poll(repo, &record, &options);
+        report.created += 1;
+    }
+
+    report
+}
+
+fn poll_options(record: &PollRecord) -> Result<Vec<PollOption>, OptionError> {
+    if record.name.is_empty() || PollStatus::from_str(&record.status).is_none() {
+        return Err(OptionError::InvalidValue);
+    }
+
+    record.options.iter()
+        .map(|&(ref key, ref value)| PollOption::parse(key, value))
+        .collect()
+}
+
+fn import_poll(repo: &PollRepository, record: &PollRecord, options: &[PollOption]) {
+    let status = PollStatus::from_str(&record.status).unwrap();
+    repo.create_poll(&record.name, status, record.creator_slack_id.as_ref().map(String::as_str), record.channel_id.as_ref().map(String::as_str));
+
+    let poll = match repo.find_poll_by_name(&record.name) {
+        Some(poll) => poll,
+        None => return
+    };
+
+    repo.update_poll_option(poll.id, &PollOption::Anonymous(record.anonymous));
+
+    for option in options.iter() {
+        repo.update_poll_option(poll.id, option);
+    }
+
+    repo.update_poll_timestamps(poll.id, record.started_at.as_ref().map(String::as_str), record.concluded_at.as_ref().map(String::as_str));
+
+    for proposal_record in record.proposals.iter() {
+        let item = match find_or_create_item(repo, &proposal_record.item) {
+            Some(item) => item,
+            None => continue
+        };
+
+        repo.insert_proposal(poll.id, item.id);
+
+        let proposal = match repo.find_proposal_by_poll_id_and_item_id(poll.id, item.id) {
+            Some(proposal) => proposal,
+            None => continue
+        };
+
+        for vote_record in proposal_record.votes.iter() {
+            if let Some(voter) = find_or_create_voter(repo, vote_record) {
+                repo.upsert_vote(voter.id, proposal.id, vote_record.weight);
+            }
+        }
+    }
+}
+
+fn find_or_create_item(repo: &PollRepository, record: &ItemRecord) -> Option<Item> {
+    if let Some(item) = item::find_item_by_name(repo, &record.name) {
+        return Some(item);
+    }
+
+    item::create_item(repo, &record.name);
+    let item = item::find_item_by_name(repo, &record.name);
+
+    // Details that cannot be read are left out rather than losing the votes
+    if let (Some(item), Ok(options)) = (item.as_ref(), parse_options(record)) {
+        for option in options.iter() {
+            repo.update_item_option(item.id, option);
+        }
+    }
+
+    item
+}
+
+fn find_or_create_voter(repo: &PollRepository, record: &VoteRecord) -> Option<Voter> {
+    match (record.voter_slack_id.as_ref(), record.voter_name.as_ref()) {
+        (Some(slack_id), Some(name)) => {
+            if repo.find_voter_by_slack_id(slack_id).is_none() {
+                repo.create_voter(slack_id, name);
+            }
+
+            repo.find_voter_by_slack_id(slack_id)
+        },
+        (None, Some(name)) => repo.find_voters().into_iter().find(|voter| voter.name == *name),
+        _ => None
+    }
+}
+
+fn parse_options(record: &ItemRecord) -> Result<Vec<ItemOption>, OptionError> {
+    if record.name.trim().is_empty() {
+        return Err(OptionError::InvalidValue);
+    }
+
+    record.options.iter()
+        .map(|&(ref key, ref value)| ItemOption::parse(key, value))
+        .collect()
+}
+
+// The `ItemOption` key for a column of an import file
+fn option_key(column: &str) -> Option<&'static str> {
+    match column {
+        "category" | "cuisine" => Some("category"),
+        "address" => Some("address"),
+        "price_level" | "price" => Some("price"),
+        "menu_url" | "menu" | "url" => Some("menu"),
+        "vegetarian" => Some("vegetarian"),
+        "vegan" => Some("vegan"),
+        "tags" => Some("tags"),
+        _ => None
+    }
+}
+
+fn is_json(content: &str) -> bool {
+    let content = content.trim();
+    content.starts_with('[') || content.starts_with('{')
+}
+
+fn items_from_json(content: &str) -> Vec<Result<ItemRecord, ()>> {
+    let json: Value = match serde_json::from_str(content) {
+        Ok(json) => json,
+        Err(_) => return vec![Err(())]
+    };
+
+    // Either a plain list of places or an object with an `items` list
+    let entries = json.as_array().or_else(|| json.get("items").and_then(Value::as_array));
+
+    match entries {
+        Some(entries) => entries.iter().map(item_from_json).collect(),
+        None => vec![Err(())]
+    }
+}
+
+fn item_from_json(json: &Value) -> Result<ItemRecord, ()> {
+    let object = json.as_object().ok_or(())?;
+    let name = object.get("name").and_then(Value::as_str).ok_or(())?;
+
+    let options = object.iter()
+        .filter_map(|(key, value)| option_key(key).and_then(|key| json_text(value).map(|text| (key.to_owned(), text))))
+        .collect();
+
+    Ok(ItemRecord { name: name.to_owned(), options: options })
+}
+
+fn polls_from_json(content: &str) -> Vec<Result<PollRecord, ()>> {
+    let json: Value = match serde_json::from_str(content) {
+        Ok(json) => json,
+        Err(_) => return vec![Err(())]
+    };
+
+    match json.get("polls").and_then(Value::as_array) {
+        Some(polls) => polls.iter().map(poll_from_json).collect(),
+        None => vec![Err(())]
+    }
+}
+
+fn poll_from_json(json: &Value) -> Result<PollRecord, ()> {
+    let text = |key: &str| json.get(key).and_then(json_text);
+
+    let mut proposals = Vec::new();
+
+    for proposal in json.get("proposals").and_then(Value::as_array).ok_or(())?.iter() {
+        let mut votes = Vec::new();
+
+        for vote in proposal.get("votes").and_then(Value::as_array).map_or(&[][..], Vec::as_slice) {
+            let voter = vote.get("voter");
+
+            votes.push(VoteRecord {
+                voter_name: voter.and_then(|voter| voter.get("name")).and_then(json_text),
+                voter_slack_id: voter.and_then(|voter| voter.get("slack_id")).and_then(json_text),
+                weight: vote.get("weight").and_then(Value::as_i64).ok_or(())? as i32
+            });
+        }
+
+        proposals.push(ProposalRecord {
+            item: item_from_json(proposal.get("item").ok_or(())?)?,
+            votes: votes
+        });
+    }
+
+    let options = POLL_OPTION_KEYS.iter()
+        .filter_map(|key| text(*key).map(|value| ((*key).to_owned(), value)))
+        .collect();
+
+    Ok(PollRecord {
+        name: text("name").ok_or(())?,
+        status: text("status").ok_or(())?,
+        anonymous: json.get("anonymous").and_then(Value::as_bool).unwrap_or(false),
+        options: options,
+        creator_slack_id: text("creator_slack_id"),
+        channel_id: text("channel_id"),
+        started_at: text("started_at"),
+        concluded_at: text("concluded_at"),
+        proposals: proposals
+    })
+}
+
+// Values as they would be typed after `key=`, lists separated by commas
+fn json_text(value: &Value) -> Option<String> {
+    match value {
+        &Value::Null => None,
+        &Value::String(ref text) => Some(text.clone()),
+        &Value::Array(ref values) => Some(values.iter().filter_map(json_text).collect::<Vec<_>>().join(",")),
+        other => Some(other.to_string())
+    }
+}
+
+/// A CSV file as rows of fields. Quoted fields may contain commas, line
+/// breaks and quotes written twice. Empty lines are left out.
+pub fn parse_csv(content: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                },
+                '"' => in_quotes = false,
+                _ => field.push(c)
+            }
+        } else {
+            match c {
+                '"' => in_quotes = true,
+                ',' => row.push(mem::replace(&mut field, String::new())),
+                '\r' => (),
+                '\n' => {
+                    row.push(mem::replace(&mut field, String::new()));
+                    rows.push(mem::replace(&mut row, Vec::new()));
+                },
+                _ => field.push(c)
+            }
+        }
+    }
+
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    rows.into_iter()
+        .filter(|row| row.iter().any(|field| !field.trim().is_empty()))
+        .collect()
+}
+
+// The rows after the header as maps from column name to value. Rows with
+// more fields than the header are rejected, missing fields are left out.
+fn csv_records(content: &str) -> Vec<Result<HashMap<String, String>, ()>> {
+    let mut rows = parse_csv(content).into_iter();
+
+    let header: Vec<String> = match rows.next() {
+        Some(header) => header.iter().map(|column| column.trim().to_lowercase()).collect(),
+        None => return Vec::new()
+    };
+
+    rows.map(|row| if row.len() > header.len() {
+            Err(())
+        } else {
+            Ok(header.iter().cloned().zip(row.into_iter()).collect())
+        })
+        .collect()
+}
+
+fn csv_text(record: &HashMap<String, String>, column: &str) -> Option<String> {
+    record.get(column)
+        .map(|value| value.trim().to_owned())
+        .and_then(|value| if value.is_empty() { None } else { Some(value) })
+}
+
+fn item_from_csv(record: &HashMap<String, String>) -> Result<ItemRecord, ()> {
+    // Exports name the place `item`
+    let name = csv_text(record, "name").or_else(|| csv_text(record, "item")).ok_or(())?;
+
+    let options = record.iter()
+        .filter_map(|(column, value)| option_key(column).map(|key| (key.to_owned(), value.trim().to_owned())))
+        .filter(|&(_, ref value)| !value.is_empty())
+        .collect();
+
+    Ok(ItemRecord { name: name, options: options })
+}
+
+fn items_from_csv(content: &str) -> Vec<Result<ItemRecord, ()>> {
+    csv_records(content).into_iter().map(|record| record.and_then(|record| item_from_csv(&record))).collect()
+}
+
+fn polls_from_csv(content: &str) -> Vec<Result<PollRecord, ()>> {
+    let mut polls: Vec<Result<PollRecord, ()>> = Vec::new();
+    let mut poll_indices: HashMap<String, usize> = HashMap::new();
+
+    for record in csv_records(content) {
+        let record = match record {
+            Ok(record) => record,
+            Err(_) => {
+                polls.push(Err(()));
+                continue;
+            }
+        };
+
+        let record = &record;
+        let poll_name = csv_text(record, "poll").unwrap_or_default();
+
+        let index = *poll_indices.entry(poll_name.clone()).or_insert_with(|| {
+            polls.push(Ok(PollRecord {
+                name: poll_name.clone(),
+                status: csv_text(record, "status").unwrap_or_default(),
+                anonymous: csv_text(record, "anonymous").map_or(false, |anonymous| anonymous == "true"),
+                options: POLL_OPTION_KEYS.iter()
+                    .filter_map(|key| csv_text(record, *key).map(|value| ((*key).to_owned(), value)))
+                    .collect(),
+                creator_slack_id: csv_text(record, "creator_slack_id"),
+                channel_id: csv_text(record, "channel_id"),
+                started_at: csv_text(record, "started_at"),
+                concluded_at: csv_text(record, "concluded_at"),
+                proposals: Vec::new()
+            }));
+
+            polls.len() - 1
+        });
+
+        // One broken row makes the whole poll invalid
+        let row = item_from_csv(record).and_then(|item| match csv_text(record, "weight") {
+            Some(weight) => weight.parse::<i32>()
+                .map(|weight| (item, Some(VoteRecord { voter_name: csv_text(record, "voter"), voter_slack_id: csv_text(record, "voter_slack_id"), weight: weight })))
+                .map_err(|_| ()),
+            None => Ok((item, None))
+        });
+
+        let poll_is_valid = match (row, polls[index].as_mut()) {
+            (Ok((item, vote)), Ok(poll)) => {
+                if !poll.proposals.iter().any(|proposal| proposal.item.name == item.name) {
+                    poll.proposals.push(ProposalRecord { item: item.clone(), votes: Vec::new() });
+                }
+
+                if let Some(vote) = vote {
+                    let proposal = poll.proposals.iter_mut().find(|proposal| proposal.item.name == item.name).unwrap();
+                    proposal.votes.push(vote);
+                }
+
+                true
+            },
+            _ => false
+        };
+
+        if !poll_is_valid {
+            polls[index] = Err(());
+        }
+    }
+
+    polls
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use repository::SqliteRepository;
+
+    fn row(fields: &[&str]) -> Vec<String> {
+        fields.iter().map(|field| field.to_string()).collect()
+    }
+
+    #[test]
+    fn quoted_fields_keep_separators_quotes_and_line_breaks() {
+        let rows = parse_csv("name,tags\n\"Curry, Haus\",\"scharf,veggie\"\n\"Der \"\"Grieche\"\"\",\"Zeile 1\nZeile 2\"\n");
+
+        assert_eq!(rows, vec![
+            row(&["name", "tags"]),
+            row(&["Curry, Haus", "scharf,veggie"]),
+            row(&["Der \"Grieche\"", "Zeile 1\nZeile 2"])
+        ]);
+    }
+
+    #[test]
+    fn crlf_line_endings_and_empty_lines_are_ignored() {
+        let rows = parse_csv("name,price\r\n\r\ncurry,2\r\n,\r\npizza,\r\n");
+
+        assert_eq!(rows, vec![row(&["name", "price"]), row(&["curry", "2"]), row(&["pizza", ""])]);
+    }
+
+    #[test]
+    fn last_row_does_not_need_a_line_break() {
+        assert_eq!(parse_csv("name\ncurry"), vec![row(&["name"]), row(&["curry"])]);
+        assert_eq!(parse_csv("name\n\"\""), vec![row(&["name"])]);
+    }
+
+    #[test]
+    fn rows_with_more_fields_than_the_header_are_invalid() {
+        let records = csv_records("name,price\ncurry,2,extra\npizza\n");
+
+        assert_eq!(records.len(), 2);
+        assert!(records[0].is_err());
+        assert_eq!(records[1].as_ref().ok().and_then(|record| record.get("name")).map(String::as_str), Some("pizza"));
+        assert!(records[1].as_ref().ok().map_or(false, |record| record.get("price").is_none()));
+
+        let repo = SqliteRepository::in_memory();
+        let report = import_items(&repo, "name,price\ncurry,2,extra\npizza,1\n");
+
+        assert_eq!(report, ImportReport { created: 1, skipped: 0, invalid: vec![1] });
+    }
+}
@@ -0,0 +1,56 @@
+use matching;
+use models::*;
+use repository::PollRepository;
+
+/// Creates a new item. Item names are stored in lowercase. Returns `false` if
+/// the name or an alias is already taken, ignoring case and accents.
+pub fn create_item(repo: &PollRepository, item_name: &str) -> bool {
+    if find_item_by_name(repo, item_name).is_some() {
+        return false;
+    }
+
+    repo.create_item(item_name)
+}
+
+/// Finds an item by its name or one of its aliases, ignoring case and accents.
+pub fn find_item_by_name(repo: &PollRepository, item_name: &str) -> Option<Item> {
+    if let Some(item) = repo.find_item_by_name(&item_name.to_lowercase()) {
+        return Some(item);
+    }
+
+    let normalized = matching::normalize(item_name);
+
+    repo.find_item_by_normalized_name(&normalized)
+        .or_else(|| repo.find_item_by_alias(&normalized))
+}
+
+/// The name of the item that was probably meant by `item_name`.
+pub fn suggest_item_name(repo: &PollRepository, item_name: &str) -> Option<String> {
+    let items = repo.find_items();
+    let aliases = repo.find_item_aliases();
+
+    let candidates = items.iter().map(|item| item.name.as_str())
+        .chain(aliases.iter().map(|item_alias| item_alias.alias.as_str()));
+
+    matching::closest(item_name, candidates).map(|name| {
+        // Aliases are suggested by the name of their item
+        aliases.iter()
+            .find(|item_alias| item_alias.alias == name)
+            .and_then(|item_alias| items.iter().find(|item| item.id == item_alias.item_id))
+            .map_or(name.to_owned(), |item| item.name.clone())
+    })
+}
+
+/// Adds `alias` as another name of `item`. Returns `false` if an item with
+/// that name or alias already exists.
+pub fn add_alias(repo: &PollRepository, item: &Item, alias: &str) -> bool {
+    if find_item_by_name(repo, alias).is_some() {
+        return false;
+    }
+
+    repo.insert_item_alias(item.id, &matching::normalize(alias))
+}
+
+pub fn set_option(repo: &PollRepository, item: &Item, option: &ItemOption) -> bool {
+    repo.update_item_option(item.id, option)
+}
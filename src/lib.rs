@@ -0,0 +1,31 @@
+#[macro_use] extern crate diesel;
+#[macro_use] extern crate diesel_codegen;
+extern crate slack;
+extern crate rand;
+extern crate chrono;
+extern crate libsqlite3_sys;
+#[macro_use] extern crate serde_json;
+
+pub mod audit;
+pub mod backup;
+pub mod command;
+pub mod commands;
+pub mod export;
+pub mod i18n;
+pub mod import;
+pub mod matching;
+pub mod schema;
+pub mod models;
+pub mod personality;
+pub mod render;
+pub mod repository;
+pub mod stats;
+pub mod suggest;
+pub mod tally;
+pub mod timestamp;
+pub mod transport;
+
+pub mod item;
+pub mod poll;
+pub mod vote;
+pub mod voter;
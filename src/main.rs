@@ -1,53 +1,27 @@
-#[macro_use] extern crate diesel;
-#[macro_use] extern crate diesel_codegen;
 extern crate slack;
 extern crate dotenv;
-extern crate rand;
-
-pub mod command;
-pub mod schema;
-pub mod models;
-
-use diesel::prelude::*;
-use diesel::sqlite::SqliteConnection;
+extern crate slackbot_rs;
 
 use slack::{Channel, Event, Message, RtmClient, User};
 
 use dotenv::dotenv;
 
-use rand::Rng;
-
 use std::env;
 use std::collections::{HashSet};
-
-use self::command::{Command, Context};
-use self::models::*;
-
-// TODO: Should come from config
-const COMMAND_TOKEN: &'static str = "!";
-const NUM_LIST_POLLS: i64 = 5;
-const NUM_LIST_ITEMS: i64 = 5;
-
-static POSITIVE_COCKY_ANSWER_SUFFIXES: &'static [&str] = &[
-    "Sauber Roland!",
-    "Das ist ja schon etwas.",
-    "Einfach mal 80 fahren.",
-    "Einfach mal machen.",
-    "Nicht so viel drüber nachdenken."
-];
-
-static NEGATIVE_COCKY_ANSWER_SUFFIXES: &'static [&str] = &[
-    "Was will man machen?",
-    "¯\\_(ツ)_/¯",
-    "Da muss man erst noch ein paar Münzen einwerfen.",
-    "Da steckt man nicht drin.",
-    "Der Mensch ist des Menschen Wolf.",
-    "LANGFRISTIG wird das selbstverständlich funktionieren.",
-    "Ich nehm' das mal mit.",
-    "Ich lese das jetzt so, als ob ich hätte was machen müssen?",
-    "Junge, alles gut bei dir?",
-    "Das mach ich abends bei 'nem Bierchen."
-];
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{Duration, Instant};
+
+use slackbot_rs::backup;
+use slackbot_rs::command::{Command, Context, get_command_from_input, get_command_implementation, get_command_line, get_command_parameters};
+use slackbot_rs::commands;
+use slackbot_rs::export::{self, ExportFormat};
+use slackbot_rs::import;
+use slackbot_rs::personality::Personalities;
+use slackbot_rs::repository::{self, PollRepository};
+use slackbot_rs::transport::SlackTransport;
 
 #[allow(dead_code)]
 fn get_channel_id<'a>(cli: &'a RtmClient, channel_name: &str) -> Option<&'a Channel> {
@@ -64,65 +38,27 @@ fn get_channel_id<'a>(cli: &'a RtmClient, channel_name: &str) -> Option<&'a Chan
         })
 }
 
-fn get_command_line(whole_input: &str) -> Option<String> {
-    if whole_input.starts_with(COMMAND_TOKEN) {
-        let token_len = COMMAND_TOKEN.len();
-        return Some(whole_input[token_len..].to_owned())
-    }
-
-    None
-}
-
-fn get_command(whole_input_sans_command_token: &str) -> Option<String> {
-    let parts: Vec<_> = whole_input_sans_command_token.split_whitespace().collect();
-
-    if parts.len() > 0 {
-        return Some(parts[0].to_owned())
-    } else {
-        None
-    }
-}
-
-fn get_command_parameters(whole_input_sans_command_token: &str) -> Vec<String> {
-    let parts: Vec<_> = whole_input_sans_command_token.split_whitespace().collect();
-
-    if get_command(whole_input_sans_command_token).is_some() {
-        return parts[1..].iter().cloned().map(String::from).collect()
-    } else {
-        Vec::new()
-    }
-}
-
-fn get_command_from_input(whole_input: &str) -> Option<String> {
-    if whole_input.starts_with(COMMAND_TOKEN) {
-        let token_len = COMMAND_TOKEN.len();
-        let command_part = &whole_input[token_len..];
-
-        return get_command(command_part)
-    }
-
-    None
+struct BasicHandler {
+    pub repo: Box<PollRepository>,
+    pub commands: HashSet<Command>,
+    pub personalities: Personalities,
+    pub admins: Vec<String>,
+    pub api_key: String,
+    pub users: Vec<User>,
+    maintenance: Option<Maintenance>
 }
 
-fn get_command_implementation<'a>(command_name: &str, command_implementations: &'a HashSet<Command<'a>>) -> Option<&'a Command<'a>> {
-    for command_implementation in command_implementations {
-        if command_implementation.matches(command_name) {
-            return Some(command_implementation);
+impl slack::EventHandler for BasicHandler {
+    fn on_event(&mut self, cli: &RtmClient, event: Event) {
+        match event {
+            // Messages may be secret votes
+            Event::Message(_) => println!("on_event(event: Message)"),
+            _ => println!("on_event(event: {:?})", event)
         }
-    }
 
-    None
-}
-
-struct BasicHandler<'a> {
-    pub db_conn: SqliteConnection,
-    pub commands: HashSet<Command<'a>>,
-    pub users: Vec<User>
-}
-
-impl <'a> slack::EventHandler for BasicHandler<'a> {
-    fn on_event(&mut self, cli: &RtmClient, event: Event) {
-        println!("on_event(event: {:?})", event);
+        if let Some(ref mut maintenance) = self.maintenance {
+            maintenance.run_if_due(&*self.repo);
+        }
 
         // TODO: 1 Fetch message of users other than me
         // TODO: 2 Check if it is a command_name
@@ -159,16 +95,20 @@ impl <'a> slack::EventHandler for BasicHandler<'a> {
                     .unwrap()
                     .clone();
 
+                let user = Some(user);
+                let transport = SlackTransport::new(cli, &self.api_key);
+                let mut context = Context::new(&*self.repo, &transport, &channel_id, &user, &self.personalities)
+                    .with_admins(&self.admins)
+                    .with_input(&input);
+
                 if let Some(command_implementation) = command_implementation_option {
-                    let user = Some(user);
-                    let mut context = Context::new(&self.db_conn, cli, &channel_id, &user);
                     let enough_params = command_implementation.invoke(&mut context, command_parameters.iter().map(String::as_str).collect());
 
                     if !enough_params {
-                        let _ = cli.sender().send_message(channel_id.as_ref().unwrap().as_str(), get_cocky_answer(format!("Unzureichende Anzahl an Parametern für den Befehl '{}'.", command).as_str(), false).as_str());
+                        transport.send_message(channel_id.as_ref().unwrap().as_str(), context.answer("insufficient-parameters", &[("command", command.as_str())], false).as_str());
                     }
                 } else {
-                    let _ = cli.sender().send_message(channel_id.as_ref().unwrap().as_str(), get_cocky_answer(format!("Ich kenne den Befehl '{}' nicht.", command).as_str(), false).as_str());
+                    transport.send_message(channel_id.as_ref().unwrap().as_str(), context.answer("unknown-command", &[("command", command.as_str())], false).as_str());
                 }
             }
         }
@@ -190,840 +130,219 @@ impl <'a> slack::EventHandler for BasicHandler<'a> {
     }
 }
 
-pub fn establish_connection() -> SqliteConnection {
-    let database_url = env::var("DATABASE_URL")
-        .expect("DATABASE_URL must be set");
-    SqliteConnection::establish(&database_url)
-        .expect(&format!("Error connecting to {}", database_url))
-}
-
-pub fn create_poll(db_conn: &SqliteConnection, name: &str, status: PollStatus) -> bool {
-    use schema::polls;
-
-    if find_poll_by_name(db_conn, name).is_some() {
-        return false;
+/// `export <poll|all> <csv|json> [file]`: Writes the export to `file` or to stdout.
+fn run_export(repo: &PollRepository, args: &[String]) {
+    if args.len() < 2 {
+        println!("Usage: slackbot-rs export <poll|all> <csv|json> [file]");
+        process::exit(1);
     }
 
-    let new_poll = NewPoll {
-        name: name,
-        status: status.as_str(),
-    };
+    let format = ExportFormat::from_str(&args[1]).unwrap_or_else(|| {
+        println!("Unknown export format '{}', use csv or json.", args[1]);
+        process::exit(1);
+    });
 
-    let _ = diesel::insert(&new_poll)
-        .into(polls::table)
-        .execute(db_conn);
+    let polls = export::find_polls(repo, &args[0]).unwrap_or_else(|| {
+        println!("Cannot find poll '{}'.", args[0]);
+        process::exit(1);
+    });
 
-    true
-}
+    let content = export::export(repo, &polls, format);
 
-pub fn can_start_poll(db_conn: &SqliteConnection, poll_name: &str) -> bool {
-    use self::schema::polls::dsl::*;
+    match args.get(2) {
+        Some(file_name) => {
+            File::create(file_name)
+                .and_then(|mut file| file.write_all(content.as_bytes()))
+                .expect("Cannot write export.");
 
-    let results = polls
-        .filter(name.eq(poll_name))
-        .limit(1)
-        .load::<Poll>(db_conn)
-        .expect("Cannot load polls from DB.");
-
-    ((results.len() == 1) && (results[0].status.as_str() == PollStatus::Stopped.as_str()))
-}
-
-pub fn start_poll(db_conn: &SqliteConnection, poll_name: &str) -> bool {
-    use self::schema::polls::dsl::*;
-
-    if !find_poll_by_name(db_conn, poll_name).is_some() {
-        return false;
+            println!("Exported {} polls to {}.", polls.len(), file_name);
+        },
+        None => print!("{}", content)
     }
-
-    diesel::update(polls.filter(name.eq(poll_name)))
-        .set(status.eq(PollStatus::InProgress.as_str()))
-        .execute(db_conn)
-        .expect("Cannot update (start) poll.");
-
-    true
 }
 
-pub fn can_conclude_poll(db_conn: &SqliteConnection, poll_name: &str) -> bool {
-    use self::schema::polls::dsl::*;
-
-    let results = polls
-        .filter(name.eq(poll_name))
-        .limit(1)
-        .load::<Poll>(db_conn)
-        .expect("Cannot load polls from DB.");
-
-    ((results.len() == 1) && (results[0].status.as_str() != PollStatus::Concluded.as_str()))
-}
-
-pub fn conclude_poll(db_conn: &SqliteConnection, poll_name: &str) -> bool {
-    use self::schema::polls::dsl::*;
-
-    if !find_poll_by_name(db_conn, poll_name).is_some() {
-        return false;
+/// `import <items|history> <file>`: Imports places or the polls of an export.
+fn run_import(repo: &PollRepository, args: &[String]) {
+    if args.len() < 2 || (args[0] != "items" && args[0] != "history") {
+        println!("Usage: slackbot-rs import <items|history> <file>");
+        process::exit(1);
     }
 
-    diesel::update(polls.filter(name.eq(poll_name)))
-        .set(status.eq(PollStatus::Concluded.as_str()))
-        .execute(db_conn)
-        .expect("Cannot update (conclude) poll.");
-
-    true
-}
+    let mut content = String::new();
 
-fn create_voter(db_conn: &SqliteConnection, user_id: &str, user_name: &str) -> bool {
-    use schema::voters;
-
-    if find_voter_by_slack_id(db_conn, user_id).is_some() {
-        return false;
-    }
-
-    let new_voter = NewVoter {
-        name: user_name.to_owned(),
-        slack_id: Some(user_id.to_owned())
-    };
-
-    diesel::insert(&new_voter)
-        .into(voters::table)
-        .execute(db_conn)
-        .expect("Cannot create voter.");
-
-    true
-}
-
-fn create_item(db_conn: &SqliteConnection, item_name: &str) -> bool {
-    use schema::items;
-
-    if find_item_by_name(db_conn, item_name).is_some() {
-        return false;
-    }
+    File::open(&args[1])
+        .and_then(|mut file| file.read_to_string(&mut content))
+        .expect("Cannot read import file.");
 
-    let new_item = NewItem {
-        name: item_name.to_owned().to_lowercase(),
-    };
-
-    diesel::insert(&new_item)
-        .into(items::table)
-        .execute(db_conn)
-        .expect("Cannot create item.");
-
-    true
-}
-
-pub fn find_poll_by_name<'a>(db_conn: &'a SqliteConnection, poll_name: &'a str) -> Option<Poll> {
-    use self::schema::polls::dsl::*;
-
-    let results = polls
-        .filter(name.eq(poll_name))
-        .limit(1)
-        .load::<Poll>(db_conn)
-        .expect("Cannot load polls from DB.");
-
-    if results.len() > 0 {
-        Some(results[0].clone())
-    } else {
-        None
-    }
-}
-
-pub fn find_poll_by_id<'a>(db_conn: &'a SqliteConnection, poll_id: i32) -> Option<Poll> {
-    use self::schema::polls::dsl::*;
-
-    let results = polls
-        .filter(id.eq(poll_id))
-        .limit(1)
-        .load::<Poll>(db_conn)
-        .expect("Cannot load polls from DB.");
-
-    if results.len() > 0 {
-        Some(results[0].clone())
+    let report = if args[0] == "items" {
+        import::import_items(repo, &content)
     } else {
-        None
-    }
-}
-
-pub fn find_proposals_by_poll<'a>(db_conn: &'a SqliteConnection, poll: &'a Poll) -> Vec<Proposal> {
-    use self::schema::proposals::dsl::*;
-
-    let results = proposals
-        .filter(poll_id.eq(poll.id))
-        .load::<Proposal>(db_conn)
-        .expect("Cannot load proposals from DB.");
-
-    results.clone()
-}
-
-pub fn find_votes_by_proposal<'a>(db_conn: &'a SqliteConnection, proposal: &'a Proposal) -> Vec<Vote> {
-    use self::schema::votes::dsl::*;
-
-    let results = votes
-        .filter(proposal_id.eq(proposal.id))
-        .load::<Vote>(db_conn)
-        .expect("Cannot load votes from DB.");
+        import::import_history(repo, &content)
+    };
 
-    results.clone()
+    println!("Created {}, skipped {} existing, invalid entries: {:?}", report.created, report.skipped, report.invalid);
 }
 
-pub fn find_item_by_proposal<'a>(db_conn: &'a SqliteConnection, proposal: &'a Proposal) -> Option<Item> {
-    use self::schema::items::dsl::*;
-
-    let results = items
-        .filter(id.eq(proposal.item_id))
-        .load::<Item>(db_conn)
-        .expect("Cannot load items from DB.");
-
-    if results.len() > 0 {
-        Some(results[0].clone())
-    } else {
-        None
-    }
-}
-
-pub fn find_voter_by_vote<'a>(db_conn: &'a SqliteConnection, vote: &'a Vote) -> Option<Voter> {
-    use self::schema::voters::dsl::*;
-
-    let results = voters
-        .filter(id.eq(vote.voter_id))
-        .limit(1)
-        .load::<Voter>(db_conn)
-        .expect("Cannot load votes from DB.");
-
-    if results.len() > 0 {
-        return Some(results[0].clone())
-    } else {
-        None
+/// `backup`: Copies the database into a timestamped file in the backup directory.
+fn run_backup(database_url: &str, backup_dir: &Path) {
+    match backup::backup(database_url, backup_dir) {
+        Ok(backup_file) => println!("Backed up database to {}.", backup_file.display()),
+        Err(err) => {
+            println!("Cannot back up database: {}", err);
+            process::exit(1);
+        }
     }
 }
 
-pub fn find_item_by_name<'a>(db_conn: &'a SqliteConnection, item_name: &'a str) -> Option<Item> {
-    use self::schema::items::dsl::*;
-
-    let results = items
-        .filter(name.eq(item_name))
-        .limit(1)
-        .load::<Item>(db_conn)
-        .expect("Cannot load items from DB.");
-
-    if results.len() > 0 {
-        return Some(results[0].clone())
-    } else {
-        None
+/// `restore <file>`: Replaces the database with a backup.
+fn run_restore(database_url: &str, args: &[String]) {
+    if args.len() < 1 {
+        println!("Usage: slackbot-rs restore <file>");
+        process::exit(1);
     }
-}
 
-pub fn find_item_by_id<'a>(db_conn: &'a SqliteConnection, item_id: i32) -> Option<Item> {
-    use self::schema::items::dsl::*;
-
-    let results = items
-        .filter(id.eq(item_id))
-        .limit(1)
-        .load::<Item>(db_conn)
-        .expect("Cannot load items from DB.");
-
-    if results.len() > 0 {
-        Some(results[0].clone())
-    } else {
-        None
+    match backup::restore(Path::new(&args[0]), database_url) {
+        Ok(_) => println!("Restored database from {}.", args[0]),
+        Err(err) => {
+            println!("Cannot restore database: {}", err);
+            process::exit(1);
+        }
     }
 }
 
-fn create_proposal<'a>(db_conn: &'a SqliteConnection, poll: &'a Poll, item: &'a Item) -> bool {
-    use schema::proposals;
-
-    if !find_poll_by_id(db_conn, poll.id).is_some() {
-        return false;
-    }
-
-    if !find_item_by_id(db_conn, item.id).is_some() {
-        return false;
-    }
-
-    if find_proposal_by_poll_name_and_item_name(db_conn, poll.name.as_str(), item.name.as_str()).is_some() {
-        return false;
-    }
-
-    let new_proposal = NewProposal {
-        poll_id: poll.id,
-        item_id: item.id
+/// `prune <days> [--no-archive]`: Deletes polls concluded more than `days` days
+/// ago, after archiving them in the backup directory unless `--no-archive` is given.
+fn run_prune(repo: &PollRepository, args: &[String], backup_dir: &Path) {
+    let days = match args.get(0).and_then(|days| days.parse::<i64>().ok()) {
+        Some(days) if days >= 0 => days,
+        _ => {
+            println!("Usage: slackbot-rs prune <days> [--no-archive]");
+            process::exit(1);
+        }
     };
 
-    diesel::insert(&new_proposal)
-        .into(proposals::table)
-        .execute(db_conn)
-        .expect("Cannot create proposal.");
-
-    true
-}
-
-fn find_last_n_polls(db_conn: &SqliteConnection, num_polls: i64) -> Vec<Poll> {
-    use self::schema::polls::dsl::*;
-
-    polls
-        .order(id.desc())
-        .limit(num_polls)
-        .load::<Poll>(db_conn)
-        .expect("Error loading polls")
-}
-
-fn find_last_n_items(db_conn: &SqliteConnection, num_items: i64) -> Vec<Item> {
-    use self::schema::items::dsl::*;
-
-    items
-        .order(id.desc())
-        .limit(num_items)
-        .load::<Item>(db_conn)
-        .expect("Error loading items")
-}
-
-fn find_proposal_by_poll_name_and_item_name(db_conn: &SqliteConnection, poll_name: &str, item_name: &str) -> Option<Proposal> {
-    use self::schema::proposals::dsl::*;
-
-    let poll_option = find_poll_by_name(db_conn, poll_name);
-    let item_option = find_item_by_name(db_conn, item_name);
-
-    if !poll_option.is_some() {
-        return None;
-    }
-
-    if !item_option.is_some() {
-        return None;
-    }
-
-    let poll = poll_option.unwrap();
-    let item = item_option.unwrap();
-
-    let results = proposals
-        .filter(poll_id.eq(poll.id))
-        .filter(item_id.eq(item.id))
-        .limit(1)
-        .load::<Proposal>(db_conn)
-        .expect("Cannot load proposals from DB.");
+    let archive_dir = if args.iter().any(|arg| arg == "--no-archive") { None } else { Some(backup_dir) };
 
-    if results.len() > 0 {
-        return Some(results[0].clone())
-    } else {
-        None
+    match backup::prune_polls(repo, days, archive_dir) {
+        Ok(num_polls) => println!("Deleted {} polls concluded more than {} days ago.", num_polls, days),
+        Err(err) => {
+            println!("Cannot prune polls: {}", err);
+            process::exit(1);
+        }
     }
 }
 
-fn find_voter_by_slack_id(db_conn: &SqliteConnection, slack_id_param: &str) -> Option<Voter> {
-    use self::schema::voters::dsl::*;
-
-    let results = voters
-        .filter(slack_id.eq(slack_id_param))
-        .limit(1)
-        .load::<Voter>(db_conn)
-        .expect("Cannot load voters from DB.");
-
-    if results.len() > 0 {
-        return Some(results[0].clone())
-    } else {
-        None
-    }
+/// Backs up the database every `BACKUP_INTERVAL_HOURS` hours and then deletes
+/// polls older than `RETENTION_DAYS`, archiving them unless `RETENTION_ARCHIVE=off`.
+/// Runs between two events, so no command of the bot holds the database
+/// meanwhile. Pruning uses the bot's connection, the backup opens its own
+/// connections through SQLite's backup API, see `backup::copy_database`.
+struct Maintenance {
+    database_url: String,
+    backup_dir: PathBuf,
+    interval: Duration,
+    retention_days: Option<i64>,
+    archive: bool,
+    next_run: Instant
 }
 
-fn find_voter_by_id(db_conn: &SqliteConnection, voter_id: i32) -> Option<Voter> {
-    use self::schema::voters::dsl::*;
+impl Maintenance {
+    /// `None` unless `BACKUP_INTERVAL_HOURS` is set.
+    fn from_env(database_url: &str, backup_dir: &Path) -> Option<Maintenance> {
+        let interval_hours = match env::var("BACKUP_INTERVAL_HOURS").ok().and_then(|hours| hours.parse::<u64>().ok()) {
+            Some(hours) if hours > 0 => hours,
+            _ => return None
+        };
 
-    let results = voters
-        .filter(id.eq(voter_id))
-        .limit(1)
-        .load::<Voter>(db_conn)
-        .expect("Cannot load voters from DB.");
+        let interval = Duration::from_secs(interval_hours * 60 * 60);
 
-    if results.len() > 0 {
-        return Some(results[0].clone())
-    } else {
-        None
+        Some(Maintenance {
+            database_url: database_url.to_owned(),
+            backup_dir: backup_dir.to_owned(),
+            interval: interval,
+            retention_days: env::var("RETENTION_DAYS").ok().and_then(|days| days.parse::<i64>().ok()),
+            archive: env::var("RETENTION_ARCHIVE").map(|archive| archive != "off").unwrap_or(true),
+            next_run: Instant::now() + interval
+        })
     }
-}
-
-fn find_proposal_by_id(db_conn: &SqliteConnection, proposal_id: i32) -> Option<Proposal> {
-    use self::schema::proposals::dsl::*;
 
-    let results = proposals
-        .filter(id.eq(proposal_id))
-        .limit(1)
-        .load::<Proposal>(db_conn)
-        .expect("Cannot load proposals from DB.");
+    fn run_if_due(&mut self, repo: &PollRepository) {
+        if Instant::now() < self.next_run {
+            return;
+        }
 
-    if results.len() > 0 {
-        return Some(results[0].clone())
-    } else {
-        None
-    }
-}
+        self.next_run = Instant::now() + self.interval;
 
-fn find_vote_by_proposal_id_and_voter_id(db_conn: &SqliteConnection, proposal_id_param: i32, voter_id_param: i32) -> Option<Vote> {
-    use self::schema::votes::*;
+        match backup::backup(&self.database_url, &self.backup_dir) {
+            Ok(backup_file) => println!("[Info] Backed up database to {}.", backup_file.display()),
+            Err(err) => println!("[Warn] Cannot back up database: {}", err)
+        }
 
-    let results = dsl::votes
-        .filter(dsl::proposal_id.eq(proposal_id_param))
-        .filter(dsl::voter_id.eq(voter_id_param))
-        .limit(1)
-        .load::<Vote>(db_conn)
-        .expect("Cannot load votes from DB.");
+        if let Some(days) = self.retention_days {
+            let archive_dir = if self.archive { Some(self.backup_dir.as_path()) } else { None };
 
-    if results.len() > 0 {
-        return Some(results[0].clone())
-    } else {
-        None
+            match backup::prune_polls(repo, days, archive_dir) {
+                Ok(num_polls) => println!("[Info] Deleted {} polls concluded more than {} days ago.", num_polls, days),
+                Err(err) => println!("[Warn] Cannot prune polls: {}", err)
+            }
+        }
     }
 }
 
-fn exists_vote(db_conn: &SqliteConnection, proposal_id: i32, voter_id: i32) -> bool {
-    find_vote_by_proposal_id_and_voter_id(db_conn, proposal_id, voter_id).is_some()
-}
+fn main() {
+    dotenv().ok();
 
-fn can_vote_for_proposal(db_conn: &SqliteConnection, proposal: &Proposal) -> bool {
-    let poll_option = find_poll_by_id(db_conn, proposal.poll_id);
+    let database_url = env::var("DATABASE_URL")
+        .expect("DATABASE_URL must be set");
+    let backup_dir = PathBuf::from(env::var("BACKUP_DIR").unwrap_or(String::from("backups")));
+    let args: Vec<String> = env::args().collect();
 
-    if !poll_option.is_some() {
-        return false;
+    // Restored before connecting, the restored database is migrated on the next start
+    if args.get(1).map_or(false, |command| command == "restore") {
+        return run_restore(&database_url, &args[2..]);
     }
 
-    let poll = poll_option.unwrap();
-
-    let poll_status_option = PollStatus::from_str(poll.status.as_str());
-
-    (poll_status_option.is_some() && (poll_status_option.unwrap() == PollStatus::InProgress))
-}
-
-fn create_vote(db_conn: &SqliteConnection, voter_id: i32, proposal_id: i32, weight: i32) -> bool {
-    use schema::votes;
-
-    if !find_voter_by_id(db_conn, voter_id).is_some() {
-        return false;
+    if args.get(1).map_or(false, |command| command == "backup") {
+        return run_backup(&database_url, &backup_dir);
     }
 
-    let proposal_option = find_proposal_by_id(db_conn, proposal_id);
+    let repo = repository::establish(&database_url);
 
-    if !proposal_option.is_some() {
-        return false;
+    if env::args().any(|arg| arg == "--migrate-only") {
+        return;
     }
 
-    let proposal = proposal_option.unwrap();
-
-    if !can_vote_for_proposal(db_conn, &proposal) {
-        return false;
+    if args.get(1).map_or(false, |command| command == "export") {
+        return run_export(&*repo, &args[2..]);
     }
 
-    let new_vote = NewVote {
-        voter_id: voter_id,
-        proposal_id: proposal_id,
-        weight: weight
-    };
-
-    diesel::insert(&new_vote)
-        .into(votes::table)
-        .execute(db_conn)
-        .expect("Cannot create vote.");
-
-    true
-}
-
-fn update_vote(db_conn: &SqliteConnection, voter_id_param: i32, proposal_id_param: i32, weight_param: i32) -> bool {
-    use self::schema::votes::dsl::*;
-
-    if !find_voter_by_id(db_conn, voter_id_param).is_some() {
-        return false;
+    if args.get(1).map_or(false, |command| command == "import") {
+        return run_import(&*repo, &args[2..]);
     }
 
-    let proposal_option = find_proposal_by_id(db_conn, proposal_id_param);
-
-    if !proposal_option.is_some() {
-        return false;
+    if args.get(1).map_or(false, |command| command == "prune") {
+        return run_prune(&*repo, &args[2..], &backup_dir);
     }
 
-    let proposal = proposal_option.unwrap();
-
-    if !can_vote_for_proposal(db_conn, &proposal) {
-        return false;
-    }
-
-    diesel::update(votes
-                    .filter(voter_id.eq(voter_id_param))
-                    .filter(proposal_id.eq(proposal_id_param)))
-        .set(weight.eq(weight_param))
-        .execute(db_conn)
-        .expect("Cannot update vote.");
-
-    true
-}
-
-fn get_cocky_answer(answer: &str, is_positive: bool) -> String {
-    let suffixes = if is_positive { POSITIVE_COCKY_ANSWER_SUFFIXES } else { NEGATIVE_COCKY_ANSWER_SUFFIXES };
-
-    format!("{} {}", answer, rand::thread_rng().choose(suffixes).unwrap_or(&"")).to_owned()
-}
-
-fn main() {
-    dotenv().ok();
-
-    let new_poll = |context: &mut Context, args: Vec<&str>| -> bool {
-        if args.len() < 1 {
-            return false;
-        }
-
-        let poll_name = args[0];
-        let mut message_formatted = get_cocky_answer(format!("Umfrage '{}' angelegt.", poll_name).as_str(), true);
-
-        if let Some(channel_id) = context.channel.as_ref() {
-            let created_poll = create_poll(&context.db_conn, poll_name, PollStatus::Stopped);
-
-            if !created_poll {
-                message_formatted = get_cocky_answer(format!("Ich kann die Umfrage '{}' nicht anlegen!", poll_name).as_str(), false);
-            }
-
-            let message = message_formatted.as_str();
-            println!("{}", message);
-
-            let _ = context.cli.sender().send_message(channel_id.as_str(), message);
-        }
-
-        true
-    };
-
-    let start_poll = |context: &mut Context, args: Vec<&str>| -> bool {
-        if args.len() < 1 {
-            return false;
-        }
-
-        let poll_name = args[0];
-        let mut message_formatted = get_cocky_answer(format!("Umfrage '{}' gestartet.", poll_name).as_str(), true);
-
-        if let Some(channel_id) = context.channel.as_ref() {
-            if !find_poll_by_name(context.db_conn, poll_name).is_some() {
-                let _ = context.cli.sender().send_message(channel_id.as_str(), get_cocky_answer(format!("Ich kann keine Umfrage '{}' finden!", poll_name).as_str(), false).as_str());
-            } else {
-                if can_start_poll(&context.db_conn, poll_name) {
-                    let started_poll = start_poll(&context.db_conn, poll_name);
-
-                    if !started_poll {
-                        message_formatted = get_cocky_answer(format!("Ich kann die Umfrage '{}' nicht starten!", poll_name).as_str(), false);
-                    }
-
-                    let message = message_formatted.as_str();
-                    println!("{}", message);
-
-                    let _ = context.cli.sender().send_message(channel_id.as_str(), message);
-                } else {
-                    let _ = context.cli.sender().send_message(channel_id.as_str(), get_cocky_answer("Ich kann keine Umfrage erneut starten, sofern sie schon einmal gestartet worden ist.", false).as_str());
-                }
-            }
-        }
-
-        true
-    };
-
-    let conclude_poll = |context: &mut Context, args: Vec<&str>| -> bool {
-        if args.len() < 1 {
-            return false;
-        }
-
-        let poll_name = args[0];
-        let mut message_formatted = get_cocky_answer(format!("Umfrage beendet '{}'.", poll_name).as_str(), true);
-
-        if let Some(channel_id) = context.channel.as_ref() {
-            if can_conclude_poll(&context.db_conn, poll_name) {
-                let concluded_poll = conclude_poll(&context.db_conn, poll_name);
-
-                if !concluded_poll {
-                    message_formatted = get_cocky_answer(format!("Ich kann die Umfrage '{}' nicht beenden!", poll_name).as_str(), false);
-                }
-
-                let message = message_formatted.as_str();
-                println!("{}", message);
-
-                let _ = context.cli.sender().send_message(channel_id.as_str(), message);
-            } else {
-                let _ = context.cli.sender().send_message(channel_id.as_str(), get_cocky_answer(format!("Ich kann die Umfrage '{}' nicht beenden, weil sie entweder nicht existiert oder bereits beendet worden ist.", poll_name).as_str(), false).as_str());
-            }
-        }
-
-        true
-    };
-
-    #[allow(unused_variables)]
-    let list_polls = |context: &mut Context, args: Vec<&str>| -> bool {
-        if let Some(channel_id) = context.channel.as_ref() {
-            let results = find_last_n_polls(context.db_conn, NUM_LIST_POLLS);
-
-            println!("Displaying {} polls", results.len());
-
-            let mut message = format!("Die letzten {} Umfragen:\n", results.len());
-
-            for (num, poll) in results.iter().enumerate() {
-                message = format!("{}{}. {} ({})\n", message, (num + 1), poll.name, poll.status);
-            }
-
-            let _ = context.cli.sender().send_message(channel_id.as_str(), message.as_str());
-        }
-
-        true
-    };
-
-    #[allow(unused_variables)]
-    let list_items = |context: &mut Context, args: Vec<&str>| -> bool {
-        if let Some(channel_id) = context.channel.as_ref() {
-            let results = find_last_n_items(context.db_conn, NUM_LIST_ITEMS);
-
-            println!("Displaying {} items", results.len());
-
-            let mut message = format!("Die letzten {} Orte:\n", results.len());
-
-            for (num, item) in results.iter().enumerate() {
-                message = format!("{}{}. {}\n", message, (num + 1), item.name);
-            }
-
-            let _ = context.cli.sender().send_message(channel_id.as_str(), message.as_str());
-        }
-
-        true
-    };
-
-    #[allow(unused_variables)]
-    let new_voter = |context: &mut Context, args: Vec<&str>| -> bool {
-        if !context.user.is_some() {
-            println!("Error: Cannot create voter.");
-            return true;
-        }
-
-        let user = context.user.as_ref().unwrap();
-        let user_name = user.name.as_ref().unwrap();
-        let user_id = user.id.as_ref().unwrap();
-
-        let mut message_formatted = get_cocky_answer(format!("Neuer Wähler '{}' angelegt ({}).", user_name, user_id).as_str(), true);
-
-        if let Some(channel_id) = context.channel.as_ref() {
-            let created_voter = create_voter(&context.db_conn, user_id, user_name);
-
-            if !created_voter {
-                message_formatted = get_cocky_answer(format!("Wähler '{id}' ('{name}') ist bereits registriert!", id = user_id, name = user_name).as_str(), false);
-            }
-
-            let message = message_formatted.as_str();
-            println!("{}", message);
-
-            let _ = context.cli.sender().send_message(channel_id.as_str(), message);
-        }
-
-        true
-    };
-
-    let new_item = |context: &mut Context, args: Vec<&str>| -> bool {
-        if args.len() < 1 {
-            return false;
-        }
-
-        let item_name = args[0];
-        let mut message_formatted = get_cocky_answer(format!("Ort '{}' angelegt.", item_name).as_str(), true);
-
-        if let Some(channel_id) = context.channel.as_ref() {
-            let created_item = create_item(&context.db_conn, item_name);
-
-            if !created_item {
-                message_formatted = get_cocky_answer(format!("Ort '{}' gibt es bereits!", item_name).as_str(), false);
-            }
-
-            let message = message_formatted.as_str();
-            println!("{}", message);
-
-            let _ = context.cli.sender().send_message(channel_id.as_str(), message);
-        }
-
-        true
-    };
-
-    let new_proposal = |context: &mut Context, args: Vec<&str>| -> bool {
-        if args.len() < 2 {
-            return false;
-        }
-
-        let poll_name = args[0];
-        let item_name = args[1];
-        let mut message_formatted = get_cocky_answer(format!("Vorschlag '{}' bei '{}' angelegt.", poll_name, item_name).as_str(), true);
-
-        if let Some(channel_id) = context.channel.as_ref() {
-            let poll_option = find_poll_by_name(context.db_conn, poll_name);
-            let item_option = find_item_by_name(context.db_conn, item_name);
-
-            if poll_option.is_none() {
-                let _ = context.cli.sender().send_message(channel_id.as_str(), get_cocky_answer(format!("Ich kann keine Umfrage namens '{}' finden!", poll_name).as_str(), false).as_str());
-                return true;
-            }
-
-            if item_option.is_none() {
-                let _ = context.cli.sender().send_message(channel_id.as_str(), get_cocky_answer(format!("Ich kann keinen Ort namens '{}' finden!", item_name).as_str(), false).as_str());
-                return true;
-            }
-
-            let poll = poll_option.unwrap();
-            let item = item_option.unwrap();
-
-            let created_proposal = create_proposal(&context.db_conn, &poll, &item);
-
-            if !created_proposal {
-                message_formatted = get_cocky_answer(format!("Der Vorschlag für '{}' bei '{}' existiert bereits!", poll.name, item.name).as_str(), false);
-            }
-
-            let message = message_formatted.as_str();
-            println!("{}", message);
-
-            let _ = context.cli.sender().send_message(channel_id.as_str(), message);
-        }
-
-        true
-    };
-
-    let vote = |context: &mut Context, args: Vec<&str>| -> bool {
-        if args.len() < 3 {
-            return false;
-        }
-
-        let poll_name = args[0];
-        let item_name = args[1];
-        let weight_char = args[2];
-
-        let weight = match weight_char {
-            "-" => -1,
-            _ => 1
-        };
-        let mut message_formatted = get_cocky_answer(format!("Stimme gezählt für '{}' bei '{}' mit Gewichtung {}.", poll_name, item_name, weight).as_str(), true);
-
-        if let Some(channel_id) = context.channel.as_ref() {
-            let poll_option = find_poll_by_name(context.db_conn, poll_name);
-            let item_option = find_item_by_name(context.db_conn, item_name);
-
-            if !poll_option.is_some() || !item_option.is_some() {
-                let _ = context.cli.sender().send_message(channel_id.as_str(), get_cocky_answer(format!("Ich kann die Umfrage '{poll}' oder den Ort '{item}' nicht finden!", poll = poll_name, item = item_name).as_str(), false).as_str());
-                return true;
-            }
-
-            let poll = poll_option.unwrap();
-            let item = item_option.unwrap();
-
-            let mut proposal_option = find_proposal_by_poll_name_and_item_name(context.db_conn, poll_name, item_name);
-
-            if !proposal_option.is_some() {
-                let created_proposal = create_proposal(context.db_conn, &poll, &item);
-                proposal_option = find_proposal_by_poll_name_and_item_name(context.db_conn, poll_name, item_name);
-
-                if !created_proposal {
-                    let _ = context.cli.sender().send_message(channel_id.as_str(), get_cocky_answer(format!("Ich kann für die Umfrage '{poll}' mit Ort '{item}' keinen Vorschlag anlegen!", poll = poll_name, item = item_name).as_str(), false).as_str());
-                    return true;
-                }
-            }
-
-            let proposal = proposal_option.unwrap();
-
-            let voter_option = find_voter_by_slack_id(context.db_conn, context.user.as_ref().unwrap().id.as_ref().unwrap());
-
-            if !voter_option.is_some() {
-                let voter_name = context.user.as_ref().unwrap().name.as_ref().unwrap();
-                let _ = context.cli.sender().send_message(channel_id.as_str(), get_cocky_answer(format!("Ich kenne keinen Wähler '{name}'. Die Stimme ist nicht gezählt worden.", name = voter_name).as_str(), false).as_str());
-                return true;
-            }
-
-            let voter = voter_option.unwrap();
-
-            let vote_exists = exists_vote(context.db_conn, proposal.id, voter.id);
-
-            let set_vote = if !vote_exists {
-                create_vote(context.db_conn, voter.id, proposal.id, weight)
-            } else {
-                update_vote(context.db_conn, voter.id, proposal.id, weight)
-            };
-
-            if !set_vote {
-                message_formatted = get_cocky_answer(format!("Ich kann die Stimme für '{poll}' bei '{item}' nicht akzeptieren!", poll = poll_name, item = item_name).as_str(), false);
-            }
-
-            let message = message_formatted.as_str();
-            println!("{}", message);
-
-            let _ = context.cli.sender().send_message(channel_id.as_str(), message);
-        }
-
-        true
-    };
-
-    let show_poll_results = |context: &mut Context, args: Vec<&str>| -> bool {
-        if args.len() < 1 {
-            return false;
-        }
-
-        let poll_name = args[0];
-
-        if let Some(channel_id) = context.channel.as_ref() {
-            // TODO: remove unwrap
-            let poll_option = find_poll_by_name(context.db_conn, poll_name);
-
-            if !poll_option.is_some() {
-                let _ = context.cli.sender().send_message(channel_id.as_str(), get_cocky_answer(format!("Ich kann keine Umfrageergebnisse für '{poll}' anzeigen, weil es die Umfrage nicht gibt!", poll = poll_name).as_str(), false).as_str());
-                return true;
-            }
-
-            let poll = poll_option.unwrap();
-
-            let proposals = find_proposals_by_poll(context.db_conn, &poll);
-
-            let mut message = format!("Hier die Umfrageergebnisse für {}:\n", poll_name);
-
-            for proposal in proposals.iter() {
-                let item_option = find_item_by_proposal(context.db_conn, &proposal);
-
-                if let Some(item) = item_option {
-                    let votes = find_votes_by_proposal(context.db_conn, &proposal);
-
-                    message = format!("{}{}:", message, item.name);
-
-                    for vote in votes.iter() {
-                        let voter_option = find_voter_by_vote(context.db_conn, &vote);
-
-                        if let Some(voter) = voter_option {
-                            message = format!("{} {}({})", message, voter.name, vote.weight);
-                        }
-                    }
-
-                    message = format!("{}\n", message);
-                }
-            }
-
-            let _ = context.cli.sender().send_message(channel_id.as_str(), message.as_str());
-        }
-
-        true
-    };
-
-    #[allow(unused_variables)]
-    let help = |context: &mut Context, args: Vec<&str>| -> bool {
-        if let Some(channel_id) = context.channel.as_ref() {
-            let _ = context.cli.sender().send_message(channel_id.as_str(), get_cocky_answer("Ich kann dir jetzt gerade nicht helfen :confused:. Frag' doch einfach eine kompetente Person?", false).as_str());
-        }
-
-        true
-    };
-
-    let mut commands: HashSet<Command> = HashSet::new();
-    commands.insert(Command::new("new_poll", Box::new(new_poll)));
-    commands.insert(Command::new("start_poll", Box::new(start_poll)));
-    commands.insert(Command::new("conclude_poll", Box::new(conclude_poll)));
-    commands.insert(Command::new("list_polls", Box::new(list_polls)));
-    commands.insert(Command::new("list_items", Box::new(list_items)));
-    commands.insert(Command::new("new_voter", Box::new(new_voter)));
-    commands.insert(Command::new("new_item", Box::new(new_item)));
-    commands.insert(Command::new("new_proposal", Box::new(new_proposal)));
-    commands.insert(Command::new("vote", Box::new(vote)));
-    commands.insert(Command::new("show_poll_results", Box::new(show_poll_results)));
-    commands.insert(Command::new("help", Box::new(help)));
+    let personalities_dir = env::var("PERSONALITIES_DIR").unwrap_or(String::from("personalities"));
+    let random_seed = env::var("RANDOM_SEED").ok().and_then(|seed| seed.parse::<usize>().ok());
+    let personalities = Personalities::load(Path::new(&personalities_dir), random_seed);
 
-    let db_conn = establish_connection();
+    let admins = env::var("ADMIN_USERS").unwrap_or(String::new())
+        .split(',')
+        .map(|admin| admin.trim().to_owned())
+        .filter(|admin| !admin.is_empty())
+        .collect();
 
     let api_key = env::var("SLACK_API_TOKEN").expect("SLACK_API_TOKEN not set.");
     let mut handler = BasicHandler {
-        db_conn: db_conn,
-        commands: commands,
-        users: Vec::new()
+        repo: repo,
+        commands: commands::create_commands(),
+        personalities: personalities,
+        admins: admins,
+        api_key: api_key.clone(),
+        users: Vec::new(),
+        maintenance: Maintenance::from_env(&database_url, &backup_dir)
     };
     let r = RtmClient::login_and_run(&api_key, &mut handler);
 
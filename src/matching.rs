@@ -0,0 +1,69 @@
+/// Folds a name for comparison: lowercase, without accents and umlauts and
+/// with surrounding whitespace removed, so `Café Müller` matches `cafe muller`.
+pub fn normalize(name: &str) -> String {
+    let mut normalized = String::new();
+
+    for c in name.trim().chars().flat_map(char::to_lowercase) {
+        match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => normalized.push('a'),
+            'ç' => normalized.push('c'),
+            'è' | 'é' | 'ê' | 'ë' => normalized.push('e'),
+            'ì' | 'í' | 'î' | 'ï' => normalized.push('i'),
+            'ñ' => normalized.push('n'),
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => normalized.push('o'),
+            'ù' | 'ú' | 'û' | 'ü' => normalized.push('u'),
+            'ý' | 'ÿ' => normalized.push('y'),
+            'ß' => normalized.push_str("ss"),
+            _ => normalized.push(c)
+        }
+    }
+
+    normalized
+}
+
+/// The number of single character edits that turn `a` into `b`.
+pub fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..b.len() + 1).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + if a_char == *b_char { 0 } else { 1 };
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// The candidate that is most likely meant by `name`, if any is close enough:
+/// either a few typos away or containing the whole name.
+pub fn closest<'a, I>(name: &str, candidates: I) -> Option<&'a str>
+    where I: IntoIterator<Item = &'a str>
+{
+    let name = normalize(name);
+
+    if name.is_empty() {
+        return None;
+    }
+
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    candidates.into_iter()
+        .filter_map(|candidate| {
+            let normalized = normalize(candidate);
+            let distance = distance(&name, &normalized);
+
+            if distance <= max_distance || normalized.contains(name.as_str()) {
+                Some((distance, candidate))
+            } else {
+                None
+            }
+        })
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate)
+}
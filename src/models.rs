@@ -1,4 +1,4 @@
-use schema::{polls, items, proposals, votes, voters};
+use schema::{polls, items, item_aliases, proposals, votes, voters, settings, poll_participants, audit_log};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum PollStatus {
@@ -29,6 +29,205 @@ impl PollStatus {
     }
 }
 
+/// Who may see the results of a poll while it is running.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PollVisibility {
+    Always,
+    /// Only voters that already voted in the poll.
+    AfterVote,
+    AfterConclude
+}
+
+impl PollVisibility {
+    pub fn from_str(s: &str) -> Option<PollVisibility> {
+        match s {
+            "ALWAYS" => Some(PollVisibility::Always),
+            "AFTER_VOTE" => Some(PollVisibility::AfterVote),
+            "AFTER_CONCLUDE" => Some(PollVisibility::AfterConclude),
+            _ => None
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            &PollVisibility::Always => "ALWAYS",
+            &PollVisibility::AfterVote => "AFTER_VOTE",
+            &PollVisibility::AfterConclude => "AFTER_CONCLUDE",
+        }
+    }
+}
+
+/// Who may vote in a poll.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PollEligibility {
+    /// Everyone, unknown users are registered as voters when they vote.
+    Open,
+    Registered,
+    /// Registered voters that are members of the poll's channel.
+    Channel,
+    /// Registered voters that have been invited to the poll.
+    Invited
+}
+
+impl PollEligibility {
+    pub fn from_str(s: &str) -> Option<PollEligibility> {
+        match s {
+            "OPEN" => Some(PollEligibility::Open),
+            "REGISTERED" => Some(PollEligibility::Registered),
+            "CHANNEL" => Some(PollEligibility::Channel),
+            "INVITED" => Some(PollEligibility::Invited),
+            _ => None
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            &PollEligibility::Open => "OPEN",
+            &PollEligibility::Registered => "REGISTERED",
+            &PollEligibility::Channel => "CHANNEL",
+            &PollEligibility::Invited => "INVITED",
+        }
+    }
+}
+
+/// When a running poll concludes by itself.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AutoConclude {
+    Off,
+    /// Once every expected voter voted for at least one proposal.
+    AllVoted,
+    /// Once more than half of the expected voters voted for the same proposal.
+    Majority
+}
+
+impl AutoConclude {
+    pub fn from_str(s: &str) -> Option<AutoConclude> {
+        match s {
+            "OFF" => Some(AutoConclude::Off),
+            "ALL_VOTED" => Some(AutoConclude::AllVoted),
+            "MAJORITY" => Some(AutoConclude::Majority),
+            _ => None
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            &AutoConclude::Off => "OFF",
+            &AutoConclude::AllVoted => "ALL_VOTED",
+            &AutoConclude::Majority => "MAJORITY",
+        }
+    }
+}
+
+/// A setting of a single poll that can be changed before the poll is started.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PollOption {
+    /// Votes are only accepted via direct message and results only show sums.
+    Anonymous(bool),
+    Visibility(PollVisibility),
+    Budget(Option<i32>),
+    MaxApprovals(Option<i32>),
+    Eligibility(PollEligibility),
+    Quorum(Option<i32>),
+    AutoConclude(AutoConclude)
+}
+
+/// Why a `key=value` setting of a poll or an item was rejected.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OptionError {
+    UnknownOption,
+    InvalidValue
+}
+
+impl PollOption {
+    pub fn parse(key: &str, value: &str) -> Result<PollOption, OptionError> {
+        match key {
+            "anonymous" => parse_bool(value).map(PollOption::Anonymous),
+            "visibility" => PollVisibility::from_str(&value.to_uppercase().replace("-", "_"))
+                .map(PollOption::Visibility)
+                .ok_or(OptionError::InvalidValue),
+            "budget" => parse_limit(value).map(PollOption::Budget),
+            "max_approvals" => parse_limit(value).map(PollOption::MaxApprovals),
+            "eligibility" => PollEligibility::from_str(&value.to_uppercase())
+                .map(PollOption::Eligibility)
+                .ok_or(OptionError::InvalidValue),
+            "quorum" => parse_limit(value).map(PollOption::Quorum),
+            "auto_conclude" => AutoConclude::from_str(&value.to_uppercase().replace("-", "_"))
+                .map(PollOption::AutoConclude)
+                .ok_or(OptionError::InvalidValue),
+            _ => Err(OptionError::UnknownOption)
+        }
+    }
+
+    pub fn key(&self) -> &str {
+        match self {
+            &PollOption::Anonymous(_) => "anonymous",
+            &PollOption::Visibility(_) => "visibility",
+            &PollOption::Budget(_) => "budget",
+            &PollOption::MaxApprovals(_) => "max_approvals",
+            &PollOption::Eligibility(_) => "eligibility",
+            &PollOption::Quorum(_) => "quorum",
+            &PollOption::AutoConclude(_) => "auto_conclude",
+        }
+    }
+
+    pub fn value(&self) -> String {
+        match self {
+            &PollOption::Anonymous(anonymous) => anonymous.to_string(),
+            &PollOption::Visibility(visibility) => visibility.as_str().to_lowercase().replace("_", "-"),
+            &PollOption::Budget(limit) | &PollOption::MaxApprovals(limit) | &PollOption::Quorum(limit) => limit.map_or(String::from("none"), |limit| limit.to_string()),
+            &PollOption::Eligibility(eligibility) => eligibility.as_str().to_lowercase(),
+            &PollOption::AutoConclude(auto_conclude) => auto_conclude.as_str().to_lowercase().replace("_", "-"),
+        }
+    }
+}
+
+fn parse_bool(value: &str) -> Result<bool, OptionError> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "ja" | "on" | "1" => Ok(true),
+        "false" | "no" | "nein" | "off" | "0" => Ok(false),
+        _ => Err(OptionError::InvalidValue)
+    }
+}
+
+// A positive number or `none` to remove the limit
+fn parse_limit(value: &str) -> Result<Option<i32>, OptionError> {
+    match value {
+        "none" | "off" => Ok(None),
+        _ => match value.parse::<i32>() {
+            Ok(limit) if limit > 0 => Ok(Some(limit)),
+            _ => Err(OptionError::InvalidValue)
+        }
+    }
+}
+
+/// What a setting applies to. More specific scopes win over less specific ones.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SettingScope {
+    Workspace,
+    Channel,
+    User
+}
+
+impl SettingScope {
+    pub fn from_str(s: &str) -> Option<SettingScope> {
+        match s {
+            "WORKSPACE" => Some(SettingScope::Workspace),
+            "CHANNEL" => Some(SettingScope::Channel),
+            "USER" => Some(SettingScope::User),
+            _ => None
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            &SettingScope::Workspace => "WORKSPACE",
+            &SettingScope::Channel => "CHANNEL",
+            &SettingScope::User => "USER",
+        }
+    }
+}
+
 #[derive(Identifiable, Queryable, Associations, Clone, Debug)]
 #[has_many(proposals)]
 pub struct Poll {
@@ -36,7 +235,22 @@ pub struct Poll {
     pub name: String,
     pub status: String,
     pub started_at: Option<String>,
-    pub concluded_at: Option<String>
+    pub concluded_at: Option<String>,
+    pub anonymous: bool,
+    pub visibility: String,
+    pub creator_slack_id: Option<String>,
+    /// Points each voter can distribute over the proposals.
+    pub budget: Option<i32>,
+    /// How many proposals each voter may vote for.
+    pub max_approvals: Option<i32>,
+    pub eligibility: String,
+    /// The channel the poll was created in.
+    pub channel_id: Option<String>,
+    /// How many voters have to take part for the poll to have a winner.
+    pub quorum: Option<i32>,
+    pub auto_conclude: String,
+    /// See `matching::normalize`, `None` only until the bot has started once.
+    pub normalized_name: Option<String>
 }
 
 #[derive(Insertable, Clone)]
@@ -44,19 +258,282 @@ pub struct Poll {
 pub struct NewPoll<'a> {
     pub name: &'a str,
     pub status: &'a str,
+    pub creator_slack_id: Option<&'a str>,
+    pub channel_id: Option<&'a str>,
+    pub normalized_name: Option<&'a str>,
 }
 
 #[derive(Identifiable, Queryable, Associations, Clone, Debug)]
 #[has_many(proposals)]
 pub struct Item {
     pub id: i32,
-    pub name: String
+    pub name: String,
+    /// The cuisine, e.g. `indian`.
+    pub category: Option<String>,
+    pub address: Option<String>,
+    /// From 1 (cheap) to 4 (expensive).
+    pub price_level: Option<i32>,
+    pub menu_url: Option<String>,
+    pub vegetarian: bool,
+    pub vegan: bool,
+    /// Use `tag_list` to read them.
+    pub tags: String,
+    /// See `matching::normalize`, `None` only until the bot has started once.
+    pub normalized_name: Option<String>
+}
+
+impl Item {
+    pub fn tag_list(&self) -> Vec<&str> {
+        self.tags.split(',').filter(|tag| !tag.is_empty()).collect()
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tag_list().contains(&tag)
+    }
+}
+
+/// Formats tags the way they are stored, see `Item::tags`.
+pub fn join_tags(tags: &[&str]) -> String {
+    let tags: Vec<_> = tags.iter()
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+
+    if tags.is_empty() {
+        String::new()
+    } else {
+        format!(",{},", tags.join(","))
+    }
+}
+
+/// Another name for an item, stored normalized (see `matching::normalize`).
+#[derive(Identifiable, Queryable, Associations, Clone, Debug)]
+#[belongs_to(Item)]
+pub struct ItemAlias {
+    pub id: i32,
+    pub item_id: i32,
+    pub alias: String
+}
+
+#[derive(Insertable, Clone)]
+#[table_name="item_aliases"]
+pub struct NewItemAlias<'a> {
+    pub item_id: i32,
+    pub alias: &'a str
+}
+
+pub const MAX_PRICE_LEVEL: i32 = 4;
+
+/// Structured information about an item, set with `!item_set`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ItemOption {
+    Category(Option<String>),
+    Address(Option<String>),
+    PriceLevel(Option<i32>),
+    MenuUrl(Option<String>),
+    Vegetarian(bool),
+    Vegan(bool),
+    /// Replaces all tags, stored as returned by `join_tags`.
+    Tags(String)
+}
+
+impl ItemOption {
+    pub fn parse(key: &str, value: &str) -> Result<ItemOption, OptionError> {
+        let text = if value.is_empty() || value == "none" { None } else { Some(value.to_owned()) };
+
+        match key {
+            "category" | "cuisine" => Ok(ItemOption::Category(text.map(|category| category.to_lowercase()))),
+            "address" => Ok(ItemOption::Address(text)),
+            "price" => parse_price_level(value).map(ItemOption::PriceLevel),
+            "menu" | "url" => Ok(ItemOption::MenuUrl(text)),
+            "vegetarian" => parse_bool(value).map(ItemOption::Vegetarian),
+            "vegan" => parse_bool(value).map(ItemOption::Vegan),
+            "tags" => Ok(ItemOption::Tags(join_tags(&value.split(',').collect::<Vec<_>>()))),
+            _ => Err(OptionError::UnknownOption)
+        }
+    }
+
+    pub fn key(&self) -> &str {
+        match self {
+            &ItemOption::Category(_) => "category",
+            &ItemOption::Address(_) => "address",
+            &ItemOption::PriceLevel(_) => "price",
+            &ItemOption::MenuUrl(_) => "menu",
+            &ItemOption::Vegetarian(_) => "vegetarian",
+            &ItemOption::Vegan(_) => "vegan",
+            &ItemOption::Tags(_) => "tags",
+        }
+    }
+
+    pub fn value(&self) -> String {
+        match self {
+            &ItemOption::Category(ref text) | &ItemOption::Address(ref text) | &ItemOption::MenuUrl(ref text) =>
+                text.clone().unwrap_or(String::from("none")),
+            &ItemOption::PriceLevel(level) => level.map_or(String::from("none"), |level| "$".repeat(level as usize)),
+            &ItemOption::Vegetarian(flag) | &ItemOption::Vegan(flag) => flag.to_string(),
+            &ItemOption::Tags(ref tags) => tags.trim_matches(',').to_owned(),
+        }
+    }
+}
+
+// Either a number like `2` or as many dollar signs
+fn parse_price_level(value: &str) -> Result<Option<i32>, OptionError> {
+    let level = match value {
+        "none" | "" => return Ok(None),
+        _ if value.chars().all(|c| c == '$') => value.len() as i32,
+        _ => value.parse::<i32>().map_err(|_| OptionError::InvalidValue)?
+    };
+
+    if level >= 1 && level <= MAX_PRICE_LEVEL {
+        Ok(Some(level))
+    } else {
+        Err(OptionError::InvalidValue)
+    }
+}
+
+/// Restricts which polls are listed. Unset fields match every poll.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PollFilter {
+    pub status: Option<PollStatus>,
+    /// Part of the name, in lowercase.
+    pub search: Option<String>
+}
+
+impl PollFilter {
+    /// Adds the condition `key=value`, returns `false` for unknown keys or invalid values.
+    pub fn add(&mut self, key: &str, value: &str) -> bool {
+        match key {
+            "status" => match PollStatus::from_str(&value.to_uppercase()) {
+                Some(status) => self.status = Some(status),
+                None => return false
+            },
+            "search" => self.search = Some(value.to_lowercase()),
+            _ => return false
+        }
+
+        true
+    }
+}
+
+pub const DEFAULT_PAGE_SIZE: i64 = 5;
+pub const MAX_PAGE_SIZE: i64 = 50;
+
+/// In which order lists of polls or items are shown.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ListSort {
+    Newest,
+    Oldest,
+    Name
+}
+
+impl ListSort {
+    pub fn from_str(s: &str) -> Option<ListSort> {
+        match s {
+            "newest" => Some(ListSort::Newest),
+            "oldest" => Some(ListSort::Oldest),
+            "name" => Some(ListSort::Name),
+            _ => None
+        }
+    }
+}
+
+/// Which part of a list is shown, set with `page=`, `limit=` and `sort=`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pagination {
+    /// Starts at 1.
+    pub page: i64,
+    pub limit: i64,
+    pub sort: ListSort
+}
+
+impl Default for Pagination {
+    fn default() -> Pagination {
+        Pagination {
+            page: 1,
+            limit: DEFAULT_PAGE_SIZE,
+            sort: ListSort::Newest
+        }
+    }
+}
+
+impl Pagination {
+    /// Adds the option `key=value`, returns `false` for unknown keys or invalid values.
+    pub fn add(&mut self, key: &str, value: &str) -> bool {
+        match key {
+            "page" => match value.parse::<i64>() {
+                // Beyond this the offset of the page does not fit into an i64
+                Ok(page) if page > 0 && page <= i64::max_value() / MAX_PAGE_SIZE => self.page = page,
+                _ => return false
+            },
+            "limit" => match value.parse::<i64>() {
+                Ok(limit) if limit > 0 && limit <= MAX_PAGE_SIZE => self.limit = limit,
+                _ => return false
+            },
+            "sort" => match ListSort::from_str(&value.to_lowercase()) {
+                Some(sort) => self.sort = sort,
+                None => return false
+            },
+            _ => return false
+        }
+
+        true
+    }
+
+    /// Number of entries on the pages before this one.
+    pub fn offset(&self) -> i64 {
+        (self.page - 1) * self.limit
+    }
+
+    /// Number of pages needed to show `total` entries, at least one.
+    pub fn pages(&self, total: i64) -> i64 {
+        ((total + self.limit - 1) / self.limit).max(1)
+    }
+}
+
+/// Restricts which items are listed. Unset fields match every item.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ItemFilter {
+    /// Part of the name, in lowercase.
+    pub search: Option<String>,
+    pub tag: Option<String>,
+    pub category: Option<String>,
+    /// Vegan items are vegetarian as well.
+    pub vegetarian: bool,
+    pub vegan: bool,
+    pub max_price_level: Option<i32>
+}
+
+impl ItemFilter {
+    /// Adds the condition `key=value`, returns `false` for unknown keys or invalid values.
+    pub fn add(&mut self, key: &str, value: &str) -> bool {
+        match key {
+            "search" => self.search = Some(value.to_lowercase()),
+            "tag" => self.tag = Some(value.to_lowercase()),
+            "category" | "cuisine" => self.category = Some(value.to_lowercase()),
+            "vegetarian" | "veggie" => match parse_bool(value) {
+                Ok(flag) => self.vegetarian = flag,
+                Err(_) => return false
+            },
+            "vegan" => match parse_bool(value) {
+                Ok(flag) => self.vegan = flag,
+                Err(_) => return false
+            },
+            "price" => match parse_price_level(value) {
+                Ok(level) => self.max_price_level = level,
+                Err(_) => return false
+            },
+            _ => return false
+        }
+
+        true
+    }
 }
 
 #[derive(Insertable, Clone)]
 #[table_name="items"]
 pub struct NewItem {
-    pub name: String
+    pub name: String,
+    pub normalized_name: Option<String>
 }
 
 #[derive(Identifiable, Queryable, Associations, Clone, Debug)]
@@ -108,3 +585,119 @@ pub struct NewVoter {
     pub name: String,
     pub slack_id: Option<String> // TODO: Should not be optional and it should be unique as name can be changed!
 }
+
+#[derive(Identifiable, Queryable, Clone, Debug)]
+pub struct Setting {
+    pub id: i32,
+    pub scope: String,
+    pub scope_id: String,
+    pub name: String,
+    pub value: String
+}
+
+#[derive(Insertable, Clone)]
+#[table_name="settings"]
+pub struct NewSetting<'a> {
+    pub scope: &'a str,
+    pub scope_id: &'a str,
+    pub name: &'a str,
+    pub value: &'a str
+}
+
+#[derive(Identifiable, Queryable, Associations, Clone, Debug)]
+#[belongs_to(Poll)]
+#[belongs_to(Voter)]
+pub struct PollParticipant {
+    pub id: i32,
+    pub poll_id: i32,
+    pub voter_id: i32
+}
+
+#[derive(Insertable, Clone)]
+#[table_name="poll_participants"]
+pub struct NewPollParticipant {
+    pub poll_id: i32,
+    pub voter_id: i32
+}
+
+/// A state-changing command someone sent, see `audit::record`.
+#[derive(Identifiable, Queryable, Clone, Debug)]
+#[table_name="audit_log"]
+pub struct AuditEntry {
+    pub id: i32,
+    pub created_at: String,
+    pub slack_id: Option<String>,
+    pub voter_id: Option<i32>,
+    pub channel_id: Option<String>,
+    pub command: String,
+    /// The parameters separated by spaces.
+    pub parameters: String,
+    /// The ids of the messages the bot answered with, like `vote-counted`.
+    pub outcome: String,
+    /// The poll the command is about, if any.
+    pub poll_id: Option<i32>
+}
+
+#[derive(Insertable, Clone)]
+#[table_name="audit_log"]
+pub struct NewAuditEntry<'a> {
+    pub created_at: &'a str,
+    pub slack_id: Option<&'a str>,
+    pub voter_id: Option<i32>,
+    pub channel_id: Option<&'a str>,
+    pub command: &'a str,
+    pub parameters: &'a str,
+    pub outcome: &'a str,
+    pub poll_id: Option<i32>
+}
+
+/// How an item did in all concluded polls.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ItemStats {
+    pub name: String,
+    /// Concluded polls the item was proposed in.
+    pub proposals: i64,
+    pub wins: i64
+}
+
+impl ItemStats {
+    /// Share of the polls the item was proposed in that it won, from 0 to 100.
+    pub fn win_rate(&self) -> f64 {
+        share(self.wins, self.proposals)
+    }
+}
+
+/// How a voter took part in all concluded polls.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VoterStats {
+    pub name: String,
+    /// Concluded polls the voter voted in.
+    pub polls: i64,
+    pub votes: i64,
+    /// Votes against the final score of the proposal, e.g. downvotes of a proposal that ended up positive.
+    pub contrarian_votes: i64
+}
+
+impl VoterStats {
+    /// Share of the voter's votes that went against the final score, from 0 to 100.
+    pub fn contrarian_rate(&self) -> f64 {
+        share(self.contrarian_votes, self.votes)
+    }
+}
+
+/// The result of an item in one concluded poll.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ItemTrendPoint {
+    pub poll_name: String,
+    pub concluded_at: Option<String>,
+    pub score: i64,
+    pub won: bool
+}
+
+fn share(part: i64, total: i64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        (part as f64) * 100.0 / (total as f64)
+    }
+}
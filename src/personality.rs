@@ -0,0 +1,194 @@
+use rand::{Rng, SeedableRng, StdRng};
+
+use std::cell::{RefCell, RefMut};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+
+use i18n::{self, Locale};
+use models::SettingScope;
+use repository::PollRepository;
+
+pub const DEFAULT_PERSONALITY: &'static str = "cocky";
+
+/// Built-in pack that never adds a remark.
+pub const SERIOUS_PERSONALITY: &'static str = "serious";
+
+/// Name of the setting holding the personality of a channel or workspace.
+pub const PERSONALITY_SETTING: &'static str = "personality";
+
+static BUILTIN_PERSONALITIES: &'static [(&'static str, &'static str)] = &[
+    ("cocky", include_str!("../personalities/cocky.txt")),
+    ("neutral", include_str!("../personalities/neutral.txt")),
+    ("pirate", include_str!("../personalities/pirate.txt")),
+];
+
+/// A set of remarks the bot appends to its answers, per locale and mood.
+pub struct Personality {
+    suffixes: HashMap<(Locale, bool), Vec<String>>
+}
+
+impl Personality {
+    /// Parses a personality pack. Remarks are listed one per line below a
+    /// `[<locale>.positive]` or `[<locale>.negative]` header, lines starting
+    /// with `#` are comments.
+    pub fn parse(content: &str) -> Result<Personality, String> {
+        let mut suffixes = HashMap::new();
+        let mut section = None;
+
+        for (num, line) in content.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with("#") {
+                continue;
+            }
+
+            if line.starts_with("[") && line.ends_with("]") {
+                section = Some(parse_section(&line[1..line.len() - 1])
+                    .ok_or(format!("Unknown section '{}' in line {}.", line, num + 1))?);
+                continue;
+            }
+
+            match section {
+                Some(key) => suffixes.entry(key).or_insert_with(Vec::new).push(line.to_owned()),
+                None => return Err(format!("Remark outside of a section in line {}.", num + 1))
+            }
+        }
+
+        Ok(Personality {
+            suffixes: suffixes
+        })
+    }
+
+    fn suffixes(&self, locale: Locale, is_positive: bool) -> &[String] {
+        self.suffixes.get(&(locale, is_positive))
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+}
+
+fn parse_section(section: &str) -> Option<(Locale, bool)> {
+    let mut parts = section.splitn(2, '.');
+
+    let locale = parts.next().and_then(Locale::from_str);
+    let is_positive = match parts.next() {
+        Some("positive") => Some(true),
+        Some("negative") => Some(false),
+        _ => None
+    };
+
+    match (locale, is_positive) {
+        (Some(locale), Some(is_positive)) => Some((locale, is_positive)),
+        _ => None
+    }
+}
+
+/// All known personality packs and the random number generator used to pick
+/// remarks from them.
+pub struct Personalities {
+    packs: HashMap<String, Personality>,
+    rng: RefCell<StdRng>
+}
+
+impl Personalities {
+    /// The packs shipped with the bot. Passing a seed makes the picked remarks reproducible.
+    pub fn builtin(seed: Option<usize>) -> Personalities {
+        let rng = match seed {
+            Some(seed) => StdRng::from_seed(&[seed][..]),
+            None => StdRng::new().expect("Cannot create random number generator.")
+        };
+
+        let mut packs = HashMap::new();
+
+        for &(name, content) in BUILTIN_PERSONALITIES {
+            packs.insert(name.to_owned(), Personality::parse(content).expect("Invalid built-in personality."));
+        }
+
+        Personalities {
+            packs: packs,
+            rng: RefCell::new(rng)
+        }
+    }
+
+    /// The built-in packs plus every `<name>.txt` in `directory`. Packs from
+    /// the directory replace built-in ones of the same name.
+    pub fn load(directory: &Path, seed: Option<usize>) -> Personalities {
+        let mut personalities = Personalities::builtin(seed);
+
+        let entries = match fs::read_dir(directory) {
+            Ok(entries) => entries,
+            Err(_) => return personalities
+        };
+
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+
+            if path.extension().map_or(true, |extension| extension != "txt") {
+                continue;
+            }
+
+            let name = match path.file_stem().and_then(|stem| stem.to_str()) {
+                Some(name) => name.to_owned(),
+                None => continue
+            };
+
+            let mut content = String::new();
+
+            if let Err(err) = File::open(&path).and_then(|mut file| file.read_to_string(&mut content)) {
+                println!("[Warn] Cannot read personality {}: {}", path.display(), err);
+                continue;
+            }
+
+            match Personality::parse(&content) {
+                Ok(personality) => {
+                    personalities.packs.insert(name, personality);
+                },
+                Err(err) => println!("[Warn] Cannot load personality {}: {}", path.display(), err)
+            }
+        }
+
+        personalities
+    }
+
+    pub fn exists(&self, name: &str) -> bool {
+        name == SERIOUS_PERSONALITY || self.packs.contains_key(name)
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<_> = self.packs.keys().cloned().collect();
+        names.push(SERIOUS_PERSONALITY.to_owned());
+        names.sort();
+
+        names
+    }
+
+    /// The random number generator of the bot. Other random choices like `!pick`
+    /// use it as well, so a seed makes them reproducible, too.
+    pub fn rng(&self) -> RefMut<StdRng> {
+        self.rng.borrow_mut()
+    }
+
+    /// Appends a random remark of the pack `name` to `answer`. Unknown packs
+    /// and the serious mode leave the answer untouched.
+    pub fn decorate(&self, name: &str, locale: Locale, answer: &str, is_positive: bool) -> String {
+        let suffixes = match self.packs.get(name) {
+            Some(personality) => personality.suffixes(locale, is_positive),
+            None => return answer.to_owned()
+        };
+
+        match self.rng.borrow_mut().choose(suffixes) {
+            Some(suffix) => format!("{} {}", answer, suffix),
+            None => answer.to_owned()
+        }
+    }
+}
+
+/// Finds the personality of a channel, falling back to the workspace's and
+/// then to the default one.
+pub fn resolve_personality(repo: &PollRepository, channel_id: Option<&str>) -> String {
+    channel_id
+        .and_then(|channel_id| repo.find_setting(SettingScope::Channel, channel_id, PERSONALITY_SETTING))
+        .or_else(|| repo.find_setting(SettingScope::Workspace, i18n::WORKSPACE_SCOPE_ID, PERSONALITY_SETTING))
+        .unwrap_or(DEFAULT_PERSONALITY.to_owned())
+}
@@ -0,0 +1,138 @@
+use matching;
+use models::*;
+use repository::PollRepository;
+use tally::Tally;
+use timestamp;
+use transport::Transport;
+
+pub fn create_poll(repo: &PollRepository, poll_name: &str, creator_id: Option<&str>, channel_id: Option<&str>) -> bool {
+    repo.create_poll(poll_name, PollStatus::Stopped, creator_id, channel_id)
+}
+
+/// Finds a poll by its name, ignoring case and accents.
+pub fn find_poll_by_name(repo: &PollRepository, poll_name: &str) -> Option<Poll> {
+    if let Some(poll) = repo.find_poll_by_name(poll_name) {
+        return Some(poll);
+    }
+
+    repo.find_poll_by_normalized_name(&matching::normalize(poll_name))
+}
+
+/// The name of the poll that was probably meant by `poll_name`.
+pub fn suggest_poll_name(repo: &PollRepository, poll_name: &str) -> Option<String> {
+    let polls = repo.find_polls();
+
+    matching::closest(poll_name, polls.iter().map(|poll| poll.name.as_str())).map(str::to_owned)
+}
+
+pub fn can_start_poll(repo: &PollRepository, poll_name: &str) -> bool {
+    match repo.find_poll_by_name(poll_name) {
+        Some(poll) => poll.status.as_str() == PollStatus::Stopped.as_str(),
+        None => false
+    }
+}
+
+pub fn start_poll(repo: &PollRepository, poll_name: &str) -> bool {
+    repo.update_poll_status(poll_name, PollStatus::InProgress)
+}
+
+pub fn can_conclude_poll(repo: &PollRepository, poll_name: &str) -> bool {
+    match repo.find_poll_by_name(poll_name) {
+        Some(poll) => poll.status.as_str() != PollStatus::Concluded.as_str(),
+        None => false
+    }
+}
+
+pub fn conclude_poll(repo: &PollRepository, poll_name: &str) -> bool {
+    repo.update_poll_status(poll_name, PollStatus::Concluded)
+}
+
+pub fn is_in_progress(poll: &Poll) -> bool {
+    PollStatus::from_str(poll.status.as_str()) == Some(PollStatus::InProgress)
+}
+
+pub fn is_concluded(poll: &Poll) -> bool {
+    PollStatus::from_str(poll.status.as_str()) == Some(PollStatus::Concluded)
+}
+
+/// Polls concluded more than `days` days ago. Polls concluded before we
+/// kept the time are never expired.
+pub fn find_expired_polls(repo: &PollRepository, days: i64) -> Vec<Poll> {
+    repo.find_polls()
+        .into_iter()
+        .filter(|poll| is_concluded(poll))
+        .filter(|poll| poll.concluded_at.as_ref().and_then(|concluded_at| timestamp::days_since(concluded_at)).map_or(false, |age| age > days))
+        .collect()
+}
+
+/// Whether someone that did (not) vote in `poll` yet may see its results.
+/// The creator and admins are not restricted, see `Context::can_manage`.
+pub fn can_see_results(poll: &Poll, has_voted: bool) -> bool {
+    match PollVisibility::from_str(poll.visibility.as_str()) {
+        Some(PollVisibility::AfterVote) => has_voted || is_concluded(poll),
+        Some(PollVisibility::AfterConclude) => is_concluded(poll),
+        _ => true
+    }
+}
+
+/// Options may only change before the poll is started, so nobody can make a
+/// poll public after votes were cast in secret.
+pub fn can_change_options(poll: &Poll) -> bool {
+    PollStatus::from_str(poll.status.as_str()) == Some(PollStatus::Stopped)
+}
+
+pub fn set_option(repo: &PollRepository, poll: &Poll, option: &PollOption) -> bool {
+    if !can_change_options(poll) {
+        return false;
+    }
+
+    repo.update_poll_option(poll.id, option)
+}
+
+/// The ids of the voters that are expected to vote in `poll`, `None` if they
+/// cannot be determined.
+pub fn expected_voter_ids(repo: &PollRepository, transport: &Transport, poll: &Poll) -> Option<Vec<i32>> {
+    let voters = match PollEligibility::from_str(poll.eligibility.as_str()) {
+        Some(PollEligibility::Invited) => repo.find_participants_by_poll(poll),
+        Some(PollEligibility::Channel) if poll.channel_id.is_some() => {
+            let members = match transport.channel_members(poll.channel_id.as_ref().unwrap()) {
+                Some(members) => members,
+                None => return None
+            };
+
+            members.iter()
+                .filter_map(|member| repo.find_voter_by_slack_id(member))
+                .collect()
+        },
+        _ => repo.find_voters()
+    };
+
+    Some(voters.into_iter().map(|voter| voter.id).collect())
+}
+
+/// Whether a running poll should conclude by itself now. Returns the reason
+/// that was met.
+pub fn should_auto_conclude(repo: &PollRepository, transport: &Transport, poll: &Poll, tally: &Tally) -> Option<AutoConclude> {
+    let mode = AutoConclude::from_str(poll.auto_conclude.as_str()).unwrap_or(AutoConclude::Off);
+
+    if mode == AutoConclude::Off || !is_in_progress(poll) || !tally.has_quorum() {
+        return None;
+    }
+
+    let expected = match expected_voter_ids(repo, transport, poll) {
+        Some(ref expected) if !expected.is_empty() => expected.clone(),
+        _ => return None
+    };
+
+    let reached = match mode {
+        AutoConclude::AllVoted => expected.iter().all(|&voter_id| tally.has_voted(voter_id)),
+        AutoConclude::Majority => tally.proposals.iter().any(|proposal| proposal.positive * 2 > expected.len()),
+        AutoConclude::Off => false
+    };
+
+    if reached {
+        Some(mode)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,483 @@
+use serde_json::Value;
+
+use i18n::{self, Locale};
+use import::ImportReport;
+use models::*;
+use stats::{Overview, Trend};
+use suggest::Suggestion;
+use tally::Tally;
+
+const MAX_BAR_LENGTH: usize = 10;
+const POSITIVE_BAR_EMOJI: &'static str = ":large_blue_circle:";
+const NEGATIVE_BAR_EMOJI: &'static str = ":red_circle:";
+
+const COLOR_POSITIVE: &'static str = "good";
+const COLOR_NEGATIVE: &'static str = "danger";
+const COLOR_NEUTRAL: &'static str = "#cccccc";
+
+/// A message that Slack shows with attachments and every other transport as
+/// plain text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RichMessage {
+    pub text: String,
+    pub attachments: Vec<Attachment>,
+    /// What to show where attachments are not supported.
+    pub fallback: String
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Attachment {
+    pub title: String,
+    pub text: String,
+    pub color: &'static str,
+    pub fields: Vec<Field>
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    pub title: String,
+    pub value: String,
+    pub short: bool
+}
+
+impl RichMessage {
+    pub fn to_plain_text(&self) -> String {
+        self.fallback.clone()
+    }
+
+    /// The attachments in the format of Slack's `chat.postMessage`.
+    pub fn attachments_json(&self) -> Value {
+        Value::Array(self.attachments.iter().map(|attachment| {
+            json!({
+                "fallback": attachment.title,
+                "title": attachment.title,
+                "text": attachment.text,
+                "color": attachment.color,
+                "mrkdwn_in": ["text"],
+                "fields": attachment.fields.iter().map(|field| json!({
+                    "title": field.title,
+                    "value": field.value,
+                    "short": field.short
+                })).collect::<Vec<_>>()
+            })
+        }).collect())
+    }
+}
+
+/// How a voter is shown: as a mention if we know the Slack user, otherwise by name.
+pub fn voter_mention(voter: &Voter) -> String {
+    match voter.slack_id {
+        Some(ref slack_id) => format!("<@{}>", slack_id),
+        None => voter.name.clone()
+    }
+}
+
+pub fn status_badge(locale: Locale, status: &str) -> String {
+    let (emoji, id) = match PollStatus::from_str(status) {
+        Some(PollStatus::InProgress) => (":large_blue_circle:", "status-in-progress"),
+        Some(PollStatus::Stopped) => (":white_circle:", "status-stopped"),
+        Some(PollStatus::Concluded) => (":checkered_flag:", "status-concluded"),
+        Some(PollStatus::Aborted) => (":no_entry_sign:", "status-aborted"),
+        None => return status.to_owned()
+    };
+
+    format!("{} {}", emoji, i18n::translate(locale, id, &[]))
+}
+
+/// A bar of one emoji per vote, capped at `MAX_BAR_LENGTH` emojis per side.
+pub fn vote_bar(positive: usize, negative: usize) -> String {
+    let mut bar = String::new();
+
+    for _ in 0..positive.min(MAX_BAR_LENGTH) {
+        bar.push_str(POSITIVE_BAR_EMOJI);
+    }
+
+    for _ in 0..negative.min(MAX_BAR_LENGTH) {
+        bar.push_str(NEGATIVE_BAR_EMOJI);
+    }
+
+    bar
+}
+
+/// Everything we know about an item besides its name, comma separated.
+/// Empty if nothing is known.
+pub fn item_details(locale: Locale, item: &Item) -> String {
+    let mut details = Vec::new();
+
+    if let Some(ref category) = item.category {
+        details.push(category.clone());
+    }
+
+    if let Some(price_level) = item.price_level {
+        details.push("$".repeat(price_level as usize));
+    }
+
+    if item.vegan {
+        details.push(i18n::translate(locale, "item-vegan", &[]));
+    } else if item.vegetarian {
+        details.push(i18n::translate(locale, "item-vegetarian", &[]));
+    }
+
+    for tag in item.tag_list() {
+        details.push(format!("#{}", tag));
+    }
+
+    if let Some(ref address) = item.address {
+        details.push(address.clone());
+    }
+
+    if let Some(ref menu_url) = item.menu_url {
+        details.push(menu_url.clone());
+    }
+
+    details.join(", ")
+}
+
+/// The header of one page of a list, e.g. `Polls 6 to 10 of 12 (page 2 of 3):`.
+pub fn list_header(locale: Locale, id: &str, pagination: &Pagination, shown: usize, total: i64) -> String {
+    let first = (pagination.offset() + 1).to_string();
+    let last = (pagination.offset() + shown as i64).to_string();
+    let total_string = total.to_string();
+    let page = pagination.page.to_string();
+    let pages = pagination.pages(total).to_string();
+
+    i18n::translate(locale, id, &[("first", first.as_str()), ("last", last.as_str()), ("total", total_string.as_str()), ("page", page.as_str()), ("pages", pages.as_str())])
+}
+
+/// Items with their details, one per line, numbered from `offset + 1`.
+pub fn item_list(locale: Locale, header: &str, items: &[Item], offset: i64) -> String {
+    let mut text = format!("{}\n", header);
+
+    for (num, item) in (offset as usize..).zip(items.iter()) {
+        let details = item_details(locale, item);
+
+        if details.is_empty() {
+            text = format!("{}{}. {}\n", text, (num + 1), item.name);
+        } else {
+            text = format!("{}{}. {} ({})\n", text, (num + 1), item.name, details);
+        }
+    }
+
+    text
+}
+
+/// Suggested items with why they were picked, one per line.
+pub fn suggestions(locale: Locale, header: &str, suggestions: &[Suggestion]) -> String {
+    let mut text = format!("{}\n", header);
+
+    for (num, suggestion) in suggestions.iter().enumerate() {
+        let history = &suggestion.history;
+        let mut reasons = Vec::new();
+
+        if history.wins > 0 {
+            reasons.push(i18n::translate_plural(locale, "suggest-wins", history.wins as i64, &[]));
+        } else {
+            reasons.push(i18n::translate(locale, "suggest-never-won", &[]));
+        }
+
+        if let Some(days) = history.days_since_last_win {
+            reasons.push(i18n::translate_plural(locale, "suggest-last-win", days, &[]));
+        }
+
+        if history.recent_downvotes > 0 {
+            reasons.push(i18n::translate_plural(locale, "suggest-downvotes", history.recent_downvotes as i64, &[]));
+        }
+
+        text = format!("{}{}. {} ({})\n", text, (num + 1), suggestion.item.name, reasons.join(", "));
+    }
+
+    text
+}
+
+/// The summary shown by `!stats`.
+pub fn stats_overview(locale: Locale, overview: &Overview) -> String {
+    let mut text = format!("{}\n", i18n::translate_plural(locale, "stats-header", overview.concluded_polls as i64, &[]));
+
+    if overview.top_items.is_empty() {
+        text = format!("{}{}\n", text, i18n::translate(locale, "stats-no-winners", &[]));
+    } else {
+        let items: Vec<String> = overview.top_items.iter().map(|stats| format!("{} ({})", stats.name, stats.wins)).collect();
+        text = format!("{}{}\n", text, i18n::translate(locale, "stats-top-items", &[("items", items.join(", ").as_str())]));
+    }
+
+    let participants = format!("{:.1}", overview.average_participants);
+    let voters = overview.voters_registered.to_string();
+    let turnout = format!("{:.0}", overview.average_turnout());
+    text = format!("{}{}\n", text, i18n::translate(locale, "stats-turnout", &[("participants", participants.as_str()), ("voters", voters.as_str()), ("turnout", turnout.as_str())]));
+
+    if let Some(ref voter) = overview.most_contrarian {
+        let rate = format!("{:.0}", voter.contrarian_rate());
+        text = format!("{}{}\n", text, i18n::translate(locale, "stats-contrarian", &[("voter", voter.name.as_str()), ("rate", rate.as_str())]));
+    }
+
+    text
+}
+
+/// Wins and win rate of every item, one per line.
+pub fn item_stats(locale: Locale, header: &str, item_stats: &[ItemStats]) -> String {
+    let mut text = format!("{}\n", header);
+
+    for (num, stats) in item_stats.iter().enumerate() {
+        let wins = stats.wins.to_string();
+        let proposals = stats.proposals.to_string();
+        let rate = format!("{:.0}", stats.win_rate());
+
+        text = format!("{}{}. {}\n", text, (num + 1), i18n::translate(locale, "stats-item", &[("item", stats.name.as_str()), ("wins", wins.as_str()), ("proposals", proposals.as_str()), ("rate", rate.as_str())]));
+    }
+
+    text
+}
+
+/// Participation and contrarian votes of every voter, one per line.
+pub fn voter_stats(locale: Locale, header: &str, voter_stats: &[VoterStats], concluded_polls: usize) -> String {
+    let mut text = format!("{}\n", header);
+
+    for (num, stats) in voter_stats.iter().enumerate() {
+        let polls = stats.polls.to_string();
+        let total = concluded_polls.to_string();
+        let participation = if concluded_polls == 0 { 0.0 } else { (stats.polls as f64) * 100.0 / (concluded_polls as f64) };
+        let participation = format!("{:.0}", participation);
+        let contrarian = stats.contrarian_votes.to_string();
+        let votes = stats.votes.to_string();
+
+        text = format!("{}{}. {}\n", text, (num + 1), i18n::translate(locale, "stats-voter", &[
+            ("voter", stats.name.as_str()), ("polls", polls.as_str()), ("total", total.as_str()), ("participation", participation.as_str()),
+            ("contrarian", contrarian.as_str()), ("votes", votes.as_str())
+        ]));
+    }
+
+    text
+}
+
+/// The results of an item in the concluded polls, oldest first, and where they are heading.
+pub fn item_trend(locale: Locale, header: &str, points: &[ItemTrendPoint], trend: Trend) -> String {
+    let mut text = format!("{}\n", header);
+
+    for point in points.iter() {
+        let trophy = if point.won { " :trophy:" } else { "" };
+
+        text = match point.concluded_at {
+            // Only the date of the timestamp
+            Some(ref concluded_at) => format!("{}{} ({}): {:+}{}\n", text, point.poll_name, concluded_at.chars().take(10).collect::<String>(), point.score, trophy),
+            None => format!("{}{}: {:+}{}\n", text, point.poll_name, point.score, trophy)
+        };
+    }
+
+    let trend_id = match trend {
+        Trend::Rising => "stats-trend-rising",
+        Trend::Falling => "stats-trend-falling",
+        Trend::Steady => "stats-trend-steady"
+    };
+
+    format!("{}{}\n", text, i18n::translate(locale, trend_id, &[]))
+}
+
+/// The audit log of `poll` shown by `!audit`, one entry per line. Who voted
+/// in anonymous polls stays secret.
+pub fn audit_log(locale: Locale, header: &str, poll: &Poll, entries: &[(AuditEntry, Option<Voter>)]) -> String {
+    let mut text = format!("{}\n", header);
+
+    for &(ref entry, ref voter) in entries.iter() {
+        let who = match (voter, &entry.slack_id) {
+            _ if poll.anonymous && entry.command == "vote" => i18n::translate(locale, "audit-anonymous", &[]),
+            (&Some(ref voter), _) => voter_mention(voter),
+            (&None, &Some(ref slack_id)) => format!("<@{}>", slack_id),
+            (&None, &None) => i18n::translate(locale, "audit-unknown-user", &[])
+        };
+
+        text = format!("{}`{}` {}: `!{} {}` → {}\n", text, entry.created_at, who, entry.command, entry.parameters, entry.outcome);
+    }
+
+    text
+}
+
+/// The settings and history of a poll shown by `!poll`, one per line.
+pub fn poll_details(locale: Locale, poll: &Poll, eligibility: &str) -> String {
+    let mut lines = vec![i18n::translate(locale, "poll-details-header", &[("poll", poll.name.as_str()), ("status", status_badge(locale, &poll.status).as_str())])];
+
+    if let Some(ref creator) = poll.creator_slack_id {
+        lines.push(i18n::translate(locale, "poll-details-creator", &[("creator", format!("<@{}>", creator).as_str())]));
+    }
+
+    if let Some(ref channel) = poll.channel_id {
+        lines.push(i18n::translate(locale, "poll-details-channel", &[("channel", format!("<#{}>", channel).as_str())]));
+    }
+
+    let mut method = match poll.budget {
+        Some(points) => i18n::translate_plural(locale, "poll-details-method-budget", points as i64, &[]),
+        None => i18n::translate(locale, "poll-details-method-approval", &[])
+    };
+
+    if let Some(approvals) = poll.max_approvals {
+        method = format!("{}, {}", method, i18n::translate_plural(locale, "poll-details-max-approvals", approvals as i64, &[]));
+    }
+
+    if poll.anonymous {
+        method = format!("{}, {}", method, i18n::translate(locale, "poll-details-anonymous", &[]));
+    }
+
+    lines.push(method);
+
+    if let Some(quorum) = poll.quorum {
+        lines.push(i18n::translate_plural(locale, "poll-details-quorum", quorum as i64, &[]));
+    }
+
+    lines.push(eligibility.to_owned());
+
+    if let Some(ref started_at) = poll.started_at {
+        lines.push(i18n::translate(locale, "poll-details-started", &[("time", started_at.as_str())]));
+    }
+
+    if let Some(ref concluded_at) = poll.concluded_at {
+        lines.push(i18n::translate(locale, "poll-details-concluded", &[("time", concluded_at.as_str())]));
+    }
+
+    format!("{}\n", lines.join("\n"))
+}
+
+/// The current standings of all proposals, best first.
+pub fn standings(locale: Locale, header: &str, tally: &Tally) -> String {
+    let mut text = format!("{}\n", header);
+
+    for proposal in tally.proposals.iter() {
+        text = format!("{}{}. {} ({:+}, {:.0}%)\n", text, proposal.rank, proposal.item.name, proposal.score, proposal.percentage);
+    }
+
+    let voted = tally.voters_voted.to_string();
+    let turnout = format!("{:.0}", tally.turnout());
+
+    format!("{}{}\n", text, i18n::translate_plural(locale, "results-turnout", tally.voters_eligible as i64, &[("voted", voted.as_str()), ("turnout", turnout.as_str())]))
+}
+
+/// How many entries of an import file were created, skipped and rejected.
+pub fn import_report(locale: Locale, report: &ImportReport) -> String {
+    let created = report.created.to_string();
+    let skipped = report.skipped.to_string();
+    let invalid = report.invalid.len().to_string();
+
+    let text = i18n::translate(locale, "import-report", &[("created", created.as_str()), ("skipped", skipped.as_str()), ("invalid", invalid.as_str())]);
+
+    if report.invalid.is_empty() {
+        text
+    } else {
+        let entries: Vec<String> = report.invalid.iter().map(|num| num.to_string()).collect();
+        format!("{} {}", text, i18n::translate(locale, "import-invalid-entries", &[("entries", entries.join(", ").as_str())]))
+    }
+}
+
+/// Polls with their status, one per line, numbered from `offset + 1`.
+pub fn poll_list(locale: Locale, header: &str, polls: &[Poll], offset: i64) -> RichMessage {
+    let mut text = format!("{}\n", header);
+    let mut fallback = format!("{}\n", header);
+
+    for (num, poll) in (offset as usize..).zip(polls.iter()) {
+        text = format!("{}{}. *{}* {}\n", text, (num + 1), poll.name, status_badge(locale, &poll.status));
+        fallback = format!("{}{}. {} ({})\n", fallback, (num + 1), poll.name, i18n::translate(locale, &status_message_id(&poll.status), &[]));
+    }
+
+    RichMessage {
+        text: text,
+        attachments: Vec::new(),
+        fallback: fallback
+    }
+}
+
+fn status_message_id(status: &str) -> String {
+    format!("status-{}", status.to_lowercase().replace("_", "-"))
+}
+
+/// Who may vote in `poll`, `participants` are the invited voters.
+pub fn eligibility(locale: Locale, poll: &Poll, participants: &[Voter]) -> String {
+    match PollEligibility::from_str(poll.eligibility.as_str()) {
+        Some(PollEligibility::Open) => i18n::translate(locale, "eligibility-open", &[]),
+        Some(PollEligibility::Channel) if poll.channel_id.is_some() => {
+            let channel = format!("<#{}>", poll.channel_id.as_ref().unwrap());
+            i18n::translate(locale, "eligibility-channel", &[("channel", channel.as_str())])
+        },
+        Some(PollEligibility::Invited) => {
+            let voters: Vec<_> = participants.iter().map(voter_mention).collect();
+            i18n::translate_plural(locale, "eligibility-invited", voters.len() as i64, &[("voters", voters.join(", ").as_str())])
+        },
+        _ => i18n::translate(locale, "eligibility-registered", &[])
+    }
+}
+
+/// The results of a poll, best proposal first. Without `show_voters` only the
+/// sums are shown, which is what anonymous polls need.
+pub fn poll_results(locale: Locale, header: &str, tally: &Tally, show_voters: bool) -> RichMessage {
+    let turnout = turnout_line(locale, tally);
+
+    let mut attachments = Vec::new();
+    let mut fallback = format!("{}\n{}\n", header, turnout);
+
+    for proposal in tally.proposals.iter() {
+        let voters: Vec<_> = proposal.votes.iter()
+            .filter(|_| show_voters)
+            .map(|&(ref vote, ref voter)| format!("{} ({:+})", voter_mention(voter), vote.weight))
+            .collect();
+
+        let details = item_details(locale, &proposal.item);
+
+        let color = if proposal.score > 0 { COLOR_POSITIVE } else if proposal.score < 0 { COLOR_NEGATIVE } else { COLOR_NEUTRAL };
+
+        attachments.push(Attachment {
+            title: format!("{}. {}", proposal.rank, proposal.item.name),
+            text: if details.is_empty() {
+                format!("{}\n{}", vote_bar(proposal.positive, proposal.negative), voters.join(", "))
+            } else {
+                format!("_{}_\n{}\n{}", details, vote_bar(proposal.positive, proposal.negative), voters.join(", "))
+            },
+            color: color,
+            fields: vec![
+                Field { title: i18n::translate(locale, "results-total", &[]), value: format!("{:+}", proposal.score), short: true },
+                Field { title: i18n::translate(locale, "results-approval", &[]), value: format!("{:.0}%", proposal.percentage), short: true },
+                Field { title: i18n::translate(locale, "results-votes", &[]), value: format!("{} / {}", proposal.positive, proposal.negative), short: true },
+            ]
+        });
+
+        if details.is_empty() {
+            fallback = format!("{}{}. {} ({:+}, {:.0}%):", fallback, proposal.rank, proposal.item.name, proposal.score, proposal.percentage);
+        } else {
+            fallback = format!("{}{}. {} [{}] ({:+}, {:.0}%):", fallback, proposal.rank, proposal.item.name, details, proposal.score, proposal.percentage);
+        }
+
+        for &(ref vote, ref voter) in proposal.votes.iter().filter(|_| show_voters) {
+            fallback = format!("{} {}({})", fallback, voter.name, vote.weight);
+        }
+
+        fallback = format!("{}\n", fallback);
+    }
+
+    RichMessage {
+        text: format!("{}\n{}", header, turnout),
+        attachments: attachments,
+        fallback: fallback
+    }
+}
+
+fn turnout_line(locale: Locale, tally: &Tally) -> String {
+    let voted = tally.voters_voted.to_string();
+    let turnout = format!("{:.0}", tally.turnout());
+
+    i18n::translate_plural(locale, "results-turnout", tally.voters_eligible as i64, &[("voted", voted.as_str()), ("turnout", turnout.as_str())])
+}
+
+/// Announces the winner of a concluded poll.
+pub fn conclusion(locale: Locale, poll_name: &str, tally: &Tally) -> String {
+    if !tally.has_quorum() {
+        let voted = tally.voters_voted.to_string();
+        let quorum = tally.quorum.unwrap_or(0).to_string();
+
+        return i18n::translate(locale, "conclusion-no-quorum", &[("poll", poll_name), ("voted", voted.as_str()), ("quorum", quorum.as_str())]);
+    }
+
+    match tally.winner() {
+        Some(winner) => {
+            let score = format!("{:+}", winner.score);
+            let approval = format!("{:.0}", winner.percentage);
+
+            i18n::translate(locale, "conclusion-winner", &[("poll", poll_name), ("item", winner.item.name.as_str()), ("score", score.as_str()), ("approval", approval.as_str())])
+        },
+        None => i18n::translate(locale, "conclusion-no-winner", &[("poll", poll_name)])
+    }
+}
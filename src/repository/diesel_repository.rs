@@ -0,0 +1,825 @@
+// Case-insensitive searches, as `LIKE` is case-sensitive in PostgreSQL.
+sql_function!(lower, lower_t, (x: ::diesel::types::Text) -> ::diesel::types::Text);
+
+/// Escapes the wildcards of `LIKE` in user input, so `%` and `_` match only
+/// themselves. Patterns need `ESCAPE '\\'` for this.
+pub fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+/// Common table expressions for the statistics: the score of every proposal
+/// in a concluded poll and the proposals that won. A proposal wins like in
+/// `tally::Tally::winner`: with a positive score, ahead of all other proposals
+/// (by score, then by votes in favour) and with the quorum reached.
+pub const CONCLUDED_PROPOSALS: &'static str = "\
+    WITH proposal_scores AS ( \
+        SELECT polls.id AS poll_id, polls.name AS poll_name, polls.concluded_at AS concluded_at, polls.anonymous AS anonymous, \
+               proposals.id AS proposal_id, proposals.item_id AS item_id, \
+               COALESCE(SUM(votes.weight), 0) AS score, \
+               COUNT(CASE WHEN votes.weight > 0 THEN 1 END) AS positive \
+        FROM polls \
+        INNER JOIN proposals ON proposals.poll_id = polls.id \
+        LEFT OUTER JOIN votes ON votes.proposal_id = proposals.id \
+        WHERE polls.status = 'CONCLUDED' \
+        GROUP BY polls.id, polls.name, polls.concluded_at, polls.anonymous, proposals.id, proposals.item_id \
+    ), winners AS ( \
+        SELECT proposal_scores.proposal_id \
+        FROM proposal_scores \
+        INNER JOIN polls ON polls.id = proposal_scores.poll_id \
+        WHERE proposal_scores.score > 0 \
+          AND NOT EXISTS ( \
+              SELECT 1 FROM proposal_scores others \
+              WHERE others.poll_id = proposal_scores.poll_id \
+                AND others.proposal_id <> proposal_scores.proposal_id \
+                AND (others.score > proposal_scores.score \
+                     OR (others.score = proposal_scores.score AND others.positive >= proposal_scores.positive))) \
+          AND (polls.quorum IS NULL OR polls.quorum <= ( \
+              SELECT COUNT(DISTINCT votes.voter_id) \
+              FROM votes \
+              INNER JOIN proposals ON proposals.id = votes.proposal_id \
+              WHERE proposals.poll_id = proposal_scores.poll_id)) \
+    ) ";
+
+/// Implements `PollRepository` for a struct with a `db_conn` field holding a
+/// Diesel connection to `$backend`. The queries are the same for every backend
+/// we support, so SQLite and PostgreSQL share this implementation.
+macro_rules! diesel_repository {
+    ($repository:ident, $backend:ty) => {
+        impl $repository {
+            /// Stores the normalized names of polls and items created before
+            /// they were kept, see `matching::normalize`.
+            pub fn fill_normalized_names(&self) {
+                use schema::{items, polls};
+
+                let unnormalized_polls = polls::table
+                    .filter(polls::normalized_name.is_null())
+                    .load::<Poll>(&self.db_conn)
+                    .expect("Cannot load polls from DB.");
+
+                for poll in unnormalized_polls {
+                    diesel::update(polls::table.filter(polls::id.eq(poll.id)))
+                        .set(polls::normalized_name.eq(Some(::matching::normalize(&poll.name))))
+                        .execute(&self.db_conn)
+                        .expect("Cannot store normalized poll name.");
+                }
+
+                let unnormalized_items = items::table
+                    .filter(items::normalized_name.is_null())
+                    .load::<Item>(&self.db_conn)
+                    .expect("Cannot load items from DB.");
+
+                for item in unnormalized_items {
+                    diesel::update(items::table.filter(items::id.eq(item.id)))
+                        .set(items::normalized_name.eq(Some(::matching::normalize(&item.name))))
+                        .execute(&self.db_conn)
+                        .expect("Cannot store normalized item name.");
+                }
+            }
+
+            /// The items matching `filter`, for listing them page by page and for suggestions.
+            fn filtered_items<'a>(filter: &ItemFilter) -> ::schema::items::BoxedQuery<'a, $backend> {
+                use schema::items::dsl::*;
+                use repository::diesel_repository::{escape_like, lower};
+
+                let mut query = items.into_boxed();
+
+                if let Some(ref search) = filter.search {
+                    query = query.filter(lower(name).like(format!("%{}%", escape_like(search))).escape('\\'));
+                }
+
+                if let Some(ref tag) = filter.tag {
+                    // Tags are stored with surrounding commas, so this only matches whole tags
+                    query = query.filter(tags.like(format!("%,{},%", escape_like(tag))).escape('\\'));
+                }
+
+                if let Some(ref category_param) = filter.category {
+                    query = query.filter(category.eq(category_param.clone()));
+                }
+
+                if filter.vegan {
+                    query = query.filter(vegan.eq(true));
+                } else if filter.vegetarian {
+                    query = query.filter(vegetarian.eq(true).or(vegan.eq(true)));
+                }
+
+                if let Some(max_price_level) = filter.max_price_level {
+                    query = query.filter(price_level.le(max_price_level));
+                }
+
+                query
+            }
+        }
+
+        impl PollRepository for $repository {
+            fn create_poll(&self, poll_name: &str, poll_status: PollStatus, creator_id: Option<&str>, channel_id: Option<&str>) -> bool {
+                use schema::polls;
+
+                if self.find_poll_by_name(poll_name).is_some() {
+                    return false;
+                }
+
+                let normalized_name = ::matching::normalize(poll_name);
+
+                let new_poll = NewPoll {
+                    name: poll_name,
+                    status: poll_status.as_str(),
+                    creator_slack_id: creator_id,
+                    channel_id: channel_id,
+                    normalized_name: Some(normalized_name.as_str()),
+                };
+
+                let _ = diesel::insert(&new_poll)
+                    .into(polls::table)
+                    .execute(&self.db_conn);
+
+                true
+            }
+
+            fn find_poll_by_name(&self, poll_name: &str) -> Option<Poll> {
+                use schema::polls::dsl::*;
+
+                let results = polls
+                    .filter(name.eq(poll_name))
+                    .limit(1)
+                    .load::<Poll>(&self.db_conn)
+                    .expect("Cannot load polls from DB.");
+
+                results.into_iter().next()
+            }
+
+            fn find_poll_by_normalized_name(&self, normalized_name_param: &str) -> Option<Poll> {
+                use schema::polls::dsl::*;
+
+                let results = polls
+                    .filter(normalized_name.eq(normalized_name_param))
+                    .order(id)
+                    .limit(1)
+                    .load::<Poll>(&self.db_conn)
+                    .expect("Cannot load polls from DB.");
+
+                results.into_iter().next()
+            }
+
+            fn find_poll_by_id(&self, poll_id: i32) -> Option<Poll> {
+                use schema::polls::dsl::*;
+
+                let results = polls
+                    .filter(id.eq(poll_id))
+                    .limit(1)
+                    .load::<Poll>(&self.db_conn)
+                    .expect("Cannot load polls from DB.");
+
+                results.into_iter().next()
+            }
+
+            fn find_polls_page(&self, filter: &PollFilter, pagination: &Pagination) -> (Vec<Poll>, i64) {
+                use schema::polls::dsl::*;
+                use repository::diesel_repository::{escape_like, lower};
+
+                // The same conditions are needed for counting and loading
+                let filtered = || {
+                    let mut query = polls.into_boxed();
+
+                    if let Some(ref status_param) = filter.status {
+                        query = query.filter(status.eq(status_param.as_str().to_string()));
+                    }
+
+                    if let Some(ref search) = filter.search {
+                        query = query.filter(lower(name).like(format!("%{}%", escape_like(search))).escape('\\'));
+                    }
+
+                    query
+                };
+
+                let total = filtered()
+                    .count()
+                    .get_result::<i64>(&self.db_conn)
+                    .expect("Cannot count polls.");
+
+                let query = match pagination.sort {
+                    ListSort::Newest => filtered().order(id.desc()),
+                    ListSort::Oldest => filtered().order(id.asc()),
+                    ListSort::Name => filtered().order(name.asc())
+                };
+
+                let results = query
+                    .limit(pagination.limit)
+                    .offset(pagination.offset())
+                    .load::<Poll>(&self.db_conn)
+                    .expect("Error loading polls");
+
+                (results, total)
+            }
+
+            fn find_polls(&self) -> Vec<Poll> {
+                use schema::polls::dsl::*;
+
+                polls
+                    .order(id)
+                    .load::<Poll>(&self.db_conn)
+                    .expect("Error loading polls")
+            }
+
+            fn update_poll_status(&self, poll_name: &str, poll_status: PollStatus) -> bool {
+                use schema::polls::dsl::*;
+
+                if !self.find_poll_by_name(poll_name).is_some() {
+                    return false;
+                }
+
+                diesel::update(polls.filter(name.eq(poll_name)))
+                    .set(status.eq(poll_status.as_str()))
+                    .execute(&self.db_conn)
+                    .expect("Cannot update poll status.");
+
+                // Remember when the poll was started or concluded
+                let updated_at = match poll_status {
+                    PollStatus::InProgress => diesel::update(polls.filter(name.eq(poll_name))).set(started_at.eq(Some(::timestamp::now()))).execute(&self.db_conn),
+                    PollStatus::Concluded => diesel::update(polls.filter(name.eq(poll_name))).set(concluded_at.eq(Some(::timestamp::now()))).execute(&self.db_conn),
+                    _ => Ok(0)
+                };
+
+                updated_at.expect("Cannot update poll timestamps.");
+
+                true
+            }
+
+            fn update_poll_timestamps(&self, poll_id: i32, started: Option<&str>, concluded: Option<&str>) -> bool {
+                use schema::polls::dsl::*;
+
+                let updated = diesel::update(polls.filter(id.eq(poll_id)))
+                    .set((started_at.eq(started.map(String::from)), concluded_at.eq(concluded.map(String::from))))
+                    .execute(&self.db_conn)
+                    .expect("Cannot update poll timestamps.");
+
+                updated > 0
+            }
+
+            fn update_poll_option(&self, poll_id: i32, option: &PollOption) -> bool {
+                use schema::polls::dsl::*;
+
+                let target = polls.filter(id.eq(poll_id));
+
+                let updated = match option {
+                    &PollOption::Anonymous(value) => diesel::update(target).set(anonymous.eq(value)).execute(&self.db_conn),
+                    &PollOption::Visibility(value) => diesel::update(target).set(visibility.eq(value.as_str())).execute(&self.db_conn),
+                    &PollOption::Budget(value) => diesel::update(target).set(budget.eq(value)).execute(&self.db_conn),
+                    &PollOption::MaxApprovals(value) => diesel::update(target).set(max_approvals.eq(value)).execute(&self.db_conn),
+                    &PollOption::Eligibility(value) => diesel::update(target).set(eligibility.eq(value.as_str())).execute(&self.db_conn),
+                    &PollOption::Quorum(value) => diesel::update(target).set(quorum.eq(value)).execute(&self.db_conn),
+                    &PollOption::AutoConclude(value) => diesel::update(target).set(auto_conclude.eq(value.as_str())).execute(&self.db_conn),
+                };
+
+                updated.expect("Cannot update poll option.") > 0
+            }
+
+            fn insert_poll_participant(&self, poll_id: i32, voter_id: i32) -> bool {
+                let new_participant = NewPollParticipant {
+                    poll_id: poll_id,
+                    voter_id: voter_id
+                };
+
+                // The unique index on (poll_id, voter_id) decides whether the voter is already invited
+                match diesel::insert(&new_participant).into(::schema::poll_participants::table).execute(&self.db_conn) {
+                    Ok(_) => true,
+                    Err(DatabaseError(UniqueViolation, _)) => false,
+                    Err(err) => panic!("Cannot invite voter: {}", err)
+                }
+            }
+
+            fn find_participants_by_poll(&self, poll: &Poll) -> Vec<Voter> {
+                use schema::poll_participants::dsl::*;
+
+                let voter_ids = poll_participants
+                    .filter(poll_id.eq(poll.id))
+                    .select(voter_id)
+                    .load::<i32>(&self.db_conn)
+                    .expect("Cannot load poll participants from DB.");
+
+                ::schema::voters::table
+                    .filter(::schema::voters::id.eq_any(voter_ids))
+                    .order(::schema::voters::id)
+                    .load::<Voter>(&self.db_conn)
+                    .expect("Cannot load voters from DB.")
+            }
+
+            fn delete_poll(&self, poll_id: i32) -> bool {
+                use schema::{audit_log, poll_participants, polls, proposals, votes};
+
+                let deleted = self.db_conn.transaction::<_, diesel::result::Error, _>(|| {
+                    let proposal_ids = proposals::table
+                        .filter(proposals::poll_id.eq(poll_id))
+                        .select(proposals::id)
+                        .load::<i32>(&self.db_conn)?;
+
+                    diesel::delete(votes::table.filter(votes::proposal_id.eq_any(proposal_ids))).execute(&self.db_conn)?;
+                    diesel::delete(proposals::table.filter(proposals::poll_id.eq(poll_id))).execute(&self.db_conn)?;
+                    diesel::delete(poll_participants::table.filter(poll_participants::poll_id.eq(poll_id))).execute(&self.db_conn)?;
+                    diesel::delete(audit_log::table.filter(audit_log::poll_id.eq(poll_id))).execute(&self.db_conn)?;
+                    diesel::delete(polls::table.filter(polls::id.eq(poll_id))).execute(&self.db_conn)
+                });
+
+                deleted.expect("Cannot delete poll.") > 0
+            }
+
+            fn create_item(&self, item_name: &str) -> bool {
+                use schema::items;
+
+                if self.find_item_by_name(item_name).is_some() {
+                    return false;
+                }
+
+                let new_item = NewItem {
+                    name: item_name.to_owned().to_lowercase(),
+                    normalized_name: Some(::matching::normalize(item_name)),
+                };
+
+                diesel::insert(&new_item)
+                    .into(items::table)
+                    .execute(&self.db_conn)
+                    .expect("Cannot create item.");
+
+                true
+            }
+
+            fn find_item_by_name(&self, item_name: &str) -> Option<Item> {
+                use schema::items::dsl::*;
+
+                let results = items
+                    .filter(name.eq(item_name))
+                    .limit(1)
+                    .load::<Item>(&self.db_conn)
+                    .expect("Cannot load items from DB.");
+
+                results.into_iter().next()
+            }
+
+            fn find_item_by_normalized_name(&self, normalized_name_param: &str) -> Option<Item> {
+                use schema::items::dsl::*;
+
+                let results = items
+                    .filter(normalized_name.eq(normalized_name_param))
+                    .order(id)
+                    .limit(1)
+                    .load::<Item>(&self.db_conn)
+                    .expect("Cannot load items from DB.");
+
+                results.into_iter().next()
+            }
+
+            fn find_item_by_id(&self, item_id: i32) -> Option<Item> {
+                use schema::items::dsl::*;
+
+                let results = items
+                    .filter(id.eq(item_id))
+                    .limit(1)
+                    .load::<Item>(&self.db_conn)
+                    .expect("Cannot load items from DB.");
+
+                results.into_iter().next()
+            }
+
+            fn find_items_page(&self, filter: &ItemFilter, pagination: &Pagination) -> (Vec<Item>, i64) {
+                use schema::items::dsl::*;
+
+                // The same conditions are needed for counting and loading
+                let filtered = || Self::filtered_items(filter);
+
+                let total = filtered()
+                    .count()
+                    .get_result::<i64>(&self.db_conn)
+                    .expect("Cannot count items.");
+
+                let query = match pagination.sort {
+                    ListSort::Newest => filtered().order(id.desc()),
+                    ListSort::Oldest => filtered().order(id.asc()),
+                    ListSort::Name => filtered().order(name.asc())
+                };
+
+                let results = query
+                    .limit(pagination.limit)
+                    .offset(pagination.offset())
+                    .load::<Item>(&self.db_conn)
+                    .expect("Error loading items");
+
+                (results, total)
+            }
+
+            fn find_items_matching(&self, filter: &ItemFilter) -> Vec<Item> {
+                use schema::items::dsl::*;
+
+                Self::filtered_items(filter)
+                    .order(id)
+                    .load::<Item>(&self.db_conn)
+                    .expect("Error loading items")
+            }
+
+            fn update_item_option(&self, item_id: i32, option: &ItemOption) -> bool {
+                use schema::items::dsl::*;
+
+                let target = items.filter(id.eq(item_id));
+
+                let updated = match option {
+                    &ItemOption::Category(ref value) => diesel::update(target).set(category.eq(value.clone())).execute(&self.db_conn),
+                    &ItemOption::Address(ref value) => diesel::update(target).set(address.eq(value.clone())).execute(&self.db_conn),
+                    &ItemOption::PriceLevel(value) => diesel::update(target).set(price_level.eq(value)).execute(&self.db_conn),
+                    &ItemOption::MenuUrl(ref value) => diesel::update(target).set(menu_url.eq(value.clone())).execute(&self.db_conn),
+                    &ItemOption::Vegetarian(value) => diesel::update(target).set(vegetarian.eq(value)).execute(&self.db_conn),
+                    &ItemOption::Vegan(value) => diesel::update(target).set(vegan.eq(value)).execute(&self.db_conn),
+                    &ItemOption::Tags(ref value) => diesel::update(target).set(tags.eq(value.clone())).execute(&self.db_conn),
+                };
+
+                updated.expect("Cannot update item.") > 0
+            }
+
+            fn find_items(&self) -> Vec<Item> {
+                use schema::items::dsl::*;
+
+                items
+                    .order(id)
+                    .load::<Item>(&self.db_conn)
+                    .expect("Error loading items")
+            }
+
+            fn insert_item_alias(&self, item_id: i32, alias: &str) -> bool {
+                let new_alias = NewItemAlias {
+                    item_id: item_id,
+                    alias: alias
+                };
+
+                // The unique index on alias decides whether the alias is already taken
+                match diesel::insert(&new_alias).into(::schema::item_aliases::table).execute(&self.db_conn) {
+                    Ok(_) => true,
+                    Err(DatabaseError(UniqueViolation, _)) => false,
+                    Err(err) => panic!("Cannot create item alias: {}", err)
+                }
+            }
+
+            fn find_item_by_alias(&self, alias_param: &str) -> Option<Item> {
+                use schema::item_aliases::dsl::*;
+
+                let results = item_aliases
+                    .filter(alias.eq(alias_param))
+                    .limit(1)
+                    .load::<ItemAlias>(&self.db_conn)
+                    .expect("Cannot load item aliases from DB.");
+
+                results.into_iter().next().and_then(|item_alias| self.find_item_by_id(item_alias.item_id))
+            }
+
+            fn find_item_aliases(&self) -> Vec<ItemAlias> {
+                use schema::item_aliases::dsl::*;
+
+                item_aliases
+                    .order(id)
+                    .load::<ItemAlias>(&self.db_conn)
+                    .expect("Cannot load item aliases from DB.")
+            }
+
+            fn insert_proposal(&self, poll_id: i32, item_id: i32) -> bool {
+                use schema::proposals;
+
+                let new_proposal = NewProposal {
+                    poll_id: poll_id,
+                    item_id: item_id
+                };
+
+                // The unique index on (poll_id, item_id) decides whether the proposal already exists
+                match diesel::insert(&new_proposal).into(proposals::table).execute(&self.db_conn) {
+                    Ok(_) => true,
+                    Err(DatabaseError(UniqueViolation, _)) => false,
+                    Err(err) => panic!("Cannot create proposal: {}", err)
+                }
+            }
+
+            fn find_proposal_by_id(&self, proposal_id: i32) -> Option<Proposal> {
+                use schema::proposals::dsl::*;
+
+                let results = proposals
+                    .filter(id.eq(proposal_id))
+                    .limit(1)
+                    .load::<Proposal>(&self.db_conn)
+                    .expect("Cannot load proposals from DB.");
+
+                results.into_iter().next()
+            }
+
+            fn find_proposal_by_poll_id_and_item_id(&self, poll_id_param: i32, item_id_param: i32) -> Option<Proposal> {
+                use schema::proposals::dsl::*;
+
+                let results = proposals
+                    .filter(poll_id.eq(poll_id_param))
+                    .filter(item_id.eq(item_id_param))
+                    .limit(1)
+                    .load::<Proposal>(&self.db_conn)
+                    .expect("Cannot load proposals from DB.");
+
+                results.into_iter().next()
+            }
+
+            fn find_proposals_by_poll(&self, poll: &Poll) -> Vec<Proposal> {
+                use schema::proposals::dsl::*;
+
+                proposals
+                    .filter(poll_id.eq(poll.id))
+                    .load::<Proposal>(&self.db_conn)
+                    .expect("Cannot load proposals from DB.")
+            }
+
+            fn upsert_vote(&self, voter_id_param: i32, proposal_id_param: i32, weight_param: i32) -> bool {
+                use schema::votes::dsl::*;
+
+                let update_vote = || {
+                    diesel::update(votes
+                                    .filter(voter_id.eq(voter_id_param))
+                                    .filter(proposal_id.eq(proposal_id_param)))
+                        .set(weight.eq(weight_param))
+                        .execute(&self.db_conn)
+                        .expect("Cannot update vote.")
+                };
+
+                if update_vote() > 0 {
+                    return true;
+                }
+
+                let new_vote = NewVote {
+                    voter_id: voter_id_param,
+                    proposal_id: proposal_id_param,
+                    weight: weight_param
+                };
+
+                // If someone else inserted the vote in the meantime the unique index on
+                // (voter_id, proposal_id) rejects our insert and we update their row instead.
+                match diesel::insert(&new_vote).into(::schema::votes::table).execute(&self.db_conn) {
+                    Ok(_) => true,
+                    Err(DatabaseError(UniqueViolation, _)) => update_vote() > 0,
+                    Err(err) => panic!("Cannot create vote: {}", err)
+                }
+            }
+
+            fn find_votes_by_proposal(&self, proposal: &Proposal) -> Vec<Vote> {
+                use schema::votes::dsl::*;
+
+                votes
+                    .filter(proposal_id.eq(proposal.id))
+                    .load::<Vote>(&self.db_conn)
+                    .expect("Cannot load votes from DB.")
+            }
+
+            fn find_votes_by_poll_and_voter(&self, poll: &Poll, voter_id_param: i32) -> Vec<Vote> {
+                use schema::votes::dsl::*;
+
+                let proposal_ids: Vec<i32> = self.find_proposals_by_poll(poll)
+                    .into_iter()
+                    .map(|proposal| proposal.id)
+                    .collect();
+
+                votes
+                    .filter(voter_id.eq(voter_id_param))
+                    .filter(proposal_id.eq_any(proposal_ids))
+                    .load::<Vote>(&self.db_conn)
+                    .expect("Cannot load votes from DB.")
+            }
+
+            fn find_poll_results(&self, poll: &Poll) -> Vec<(Item, Vec<(Vote, Voter)>)> {
+                // Diesel can only join two tables at a time, so the query is written by hand.
+                // The poll id is an integer, so formatting it into the query is safe.
+                let query = sql::<(::schema::items::SqlType, Integer, Nullable<Integer>, Nullable<Integer>, Nullable<Integer>, Nullable<Text>, Nullable<Text>)>(&format!(
+                    "SELECT items.id, items.name, items.category, items.address, items.price_level, items.menu_url, items.vegetarian, items.vegan, items.tags, items.normalized_name, \
+                            proposals.id, votes.id, votes.voter_id, votes.weight, voters.name, voters.slack_id \
+                     FROM polls \
+                     INNER JOIN proposals ON proposals.poll_id = polls.id \
+                     INNER JOIN items ON items.id = proposals.item_id \
+                     LEFT OUTER JOIN votes ON votes.proposal_id = proposals.id \
+                     LEFT OUTER JOIN voters ON voters.id = votes.voter_id \
+                     WHERE polls.id = {} \
+                     ORDER BY proposals.id, votes.id", poll.id));
+
+                let rows = query
+                    .load::<(Item, i32, Option<i32>, Option<i32>, Option<i32>, Option<String>, Option<String>)>(&self.db_conn)
+                    .expect("Cannot load poll results from DB.");
+
+                let mut results: Vec<(i32, Item, Vec<(Vote, Voter)>)> = Vec::new();
+
+                for (item, proposal_id, vote_id, voter_id, weight, voter_name, voter_slack_id) in rows {
+                    let is_new_proposal = results.last().map_or(true, |&(last_proposal_id, _, _)| last_proposal_id != proposal_id);
+
+                    if is_new_proposal {
+                        results.push((proposal_id, item, Vec::new()));
+                    }
+
+                    // Votes without a (known) voter are left out like everywhere else
+                    if let (Some(vote_id), Some(voter_id), Some(weight), Some(voter_name)) = (vote_id, voter_id, weight, voter_name) {
+                        let vote = Vote { id: vote_id, voter_id: voter_id, proposal_id: proposal_id, weight: weight };
+                        let voter = Voter { id: voter_id, name: voter_name, slack_id: voter_slack_id };
+
+                        results.last_mut().unwrap().2.push((vote, voter));
+                    }
+                }
+
+                results.into_iter().map(|(_, item, votes)| (item, votes)).collect()
+            }
+
+            fn create_voter(&self, user_id: &str, user_name: &str) -> bool {
+                use schema::voters;
+
+                if self.find_voter_by_slack_id(user_id).is_some() {
+                    return false;
+                }
+
+                let new_voter = NewVoter {
+                    name: user_name.to_owned(),
+                    slack_id: Some(user_id.to_owned())
+                };
+
+                diesel::insert(&new_voter)
+                    .into(voters::table)
+                    .execute(&self.db_conn)
+                    .expect("Cannot create voter.");
+
+                true
+            }
+
+            fn find_voter_by_slack_id(&self, slack_id_param: &str) -> Option<Voter> {
+                use schema::voters::dsl::*;
+
+                let results = voters
+                    .filter(slack_id.eq(slack_id_param))
+                    .limit(1)
+                    .load::<Voter>(&self.db_conn)
+                    .expect("Cannot load voters from DB.");
+
+                results.into_iter().next()
+            }
+
+            fn find_voter_by_id(&self, voter_id: i32) -> Option<Voter> {
+                use schema::voters::dsl::*;
+
+                let results = voters
+                    .filter(id.eq(voter_id))
+                    .limit(1)
+                    .load::<Voter>(&self.db_conn)
+                    .expect("Cannot load voters from DB.");
+
+                results.into_iter().next()
+            }
+
+            fn find_voters(&self) -> Vec<Voter> {
+                use schema::voters::dsl::*;
+
+                voters
+                    .order(id)
+                    .load::<Voter>(&self.db_conn)
+                    .expect("Cannot load voters from DB.")
+            }
+
+            fn count_voters(&self) -> i64 {
+                use schema::voters::dsl::*;
+
+                voters
+                    .select(count_star())
+                    .first::<i64>(&self.db_conn)
+                    .expect("Cannot count voters.")
+            }
+
+            fn find_item_stats(&self) -> Vec<ItemStats> {
+                let query = sql::<(Text, BigInt, BigInt)>(&format!(
+                    "{} \
+                     SELECT items.name, COUNT(proposal_scores.proposal_id), COUNT(winners.proposal_id) \
+                     FROM proposal_scores \
+                     INNER JOIN items ON items.id = proposal_scores.item_id \
+                     LEFT OUTER JOIN winners ON winners.proposal_id = proposal_scores.proposal_id \
+                     GROUP BY items.id, items.name \
+                     ORDER BY COUNT(winners.proposal_id) DESC, COUNT(proposal_scores.proposal_id), items.name", ::repository::diesel_repository::CONCLUDED_PROPOSALS));
+
+                query
+                    .load::<(String, i64, i64)>(&self.db_conn)
+                    .expect("Cannot load item statistics from DB.")
+                    .into_iter()
+                    .map(|(name, proposals, wins)| ItemStats { name: name, proposals: proposals, wins: wins })
+                    .collect()
+            }
+
+            fn find_voter_stats(&self) -> Vec<VoterStats> {
+                // How someone voted in a secret poll must not show, not even in sums per voter
+                let query = sql::<(Text, BigInt, BigInt, BigInt)>(&format!(
+                    "{} \
+                     SELECT voters.name, COUNT(DISTINCT concluded_votes.poll_id), COUNT(concluded_votes.vote_id), \
+                            COUNT(CASE WHEN (concluded_votes.weight > 0 AND concluded_votes.score < 0) \
+                                         OR (concluded_votes.weight < 0 AND concluded_votes.score > 0) THEN 1 END) \
+                     FROM voters \
+                     LEFT OUTER JOIN ( \
+                         SELECT votes.id AS vote_id, votes.voter_id, votes.weight, proposal_scores.poll_id, proposal_scores.score \
+                         FROM votes \
+                         INNER JOIN proposal_scores ON proposal_scores.proposal_id = votes.proposal_id \
+                         WHERE NOT proposal_scores.anonymous \
+                     ) concluded_votes ON concluded_votes.voter_id = voters.id \
+                     GROUP BY voters.id, voters.name \
+                     ORDER BY COUNT(DISTINCT concluded_votes.poll_id) DESC, voters.name", ::repository::diesel_repository::CONCLUDED_PROPOSALS));
+
+                query
+                    .load::<(String, i64, i64, i64)>(&self.db_conn)
+                    .expect("Cannot load voter statistics from DB.")
+                    .into_iter()
+                    .map(|(name, polls, votes, contrarian_votes)| VoterStats { name: name, polls: polls, votes: votes, contrarian_votes: contrarian_votes })
+                    .collect()
+            }
+
+            fn count_participants_per_concluded_poll(&self) -> Vec<i64> {
+                let query = sql::<BigInt>(
+                    "SELECT COUNT(DISTINCT votes.voter_id) \
+                     FROM polls \
+                     LEFT OUTER JOIN proposals ON proposals.poll_id = polls.id \
+                     LEFT OUTER JOIN votes ON votes.proposal_id = proposals.id \
+                     WHERE polls.status = 'CONCLUDED' \
+                     GROUP BY polls.id \
+                     ORDER BY polls.id");
+
+                query
+                    .load::<i64>(&self.db_conn)
+                    .expect("Cannot count poll participants.")
+            }
+
+            fn find_item_trend(&self, item: &Item) -> Vec<ItemTrendPoint> {
+                // The item id is an integer, so formatting it into the query is safe.
+                let query = sql::<(Text, Nullable<Text>, BigInt, Integer)>(&format!(
+                    "{} \
+                     SELECT proposal_scores.poll_name, proposal_scores.concluded_at, proposal_scores.score, \
+                            CASE WHEN winners.proposal_id IS NULL THEN 0 ELSE 1 END \
+                     FROM proposal_scores \
+                     LEFT OUTER JOIN winners ON winners.proposal_id = proposal_scores.proposal_id \
+                     WHERE proposal_scores.item_id = {} \
+                     ORDER BY proposal_scores.poll_id", ::repository::diesel_repository::CONCLUDED_PROPOSALS, item.id));
+
+                query
+                    .load::<(String, Option<String>, i64, i32)>(&self.db_conn)
+                    .expect("Cannot load item trend from DB.")
+                    .into_iter()
+                    .map(|(poll_name, concluded_at, score, won)| ItemTrendPoint { poll_name: poll_name, concluded_at: concluded_at, score: score, won: won != 0 })
+                    .collect()
+            }
+
+            fn insert_audit_entry(&self, entry: &NewAuditEntry) -> bool {
+                diesel::insert(entry)
+                    .into(::schema::audit_log::table)
+                    .execute(&self.db_conn)
+                    .expect("Cannot write audit log.") > 0
+            }
+
+            fn find_audit_entries_by_poll(&self, poll_id_param: i32) -> Vec<AuditEntry> {
+                use schema::audit_log::dsl::*;
+
+                audit_log
+                    .filter(poll_id.eq(poll_id_param))
+                    .order(id)
+                    .load::<AuditEntry>(&self.db_conn)
+                    .expect("Cannot load audit log from DB.")
+            }
+
+            fn find_setting(&self, scope_param: SettingScope, scope_id_param: &str, name_param: &str) -> Option<String> {
+                use schema::settings::dsl::*;
+
+                let results = settings
+                    .filter(scope.eq(scope_param.as_str()))
+                    .filter(scope_id.eq(scope_id_param))
+                    .filter(name.eq(name_param))
+                    .limit(1)
+                    .load::<Setting>(&self.db_conn)
+                    .expect("Cannot load settings from DB.");
+
+                results.into_iter().next().map(|setting| setting.value)
+            }
+
+            fn set_setting(&self, scope_param: SettingScope, scope_id_param: &str, name_param: &str, value_param: &str) -> bool {
+                use schema::settings::dsl::*;
+
+                let update_setting = || {
+                    diesel::update(settings
+                                    .filter(scope.eq(scope_param.as_str()))
+                                    .filter(scope_id.eq(scope_id_param))
+                                    .filter(name.eq(name_param)))
+                        .set(value.eq(value_param))
+                        .execute(&self.db_conn)
+                        .expect("Cannot update setting.")
+                };
+
+                if update_setting() > 0 {
+                    return true;
+                }
+
+                let new_setting = NewSetting {
+                    scope: scope_param.as_str(),
+                    scope_id: scope_id_param,
+                    name: name_param,
+                    value: value_param
+                };
+
+                match diesel::insert(&new_setting).into(::schema::settings::table).execute(&self.db_conn) {
+                    Ok(_) => true,
+                    Err(DatabaseError(UniqueViolation, _)) => update_setting() > 0,
+                    Err(err) => panic!("Cannot create setting: {}", err)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use models::*;
+
+#[macro_use]
+mod diesel_repository;
+
+pub mod pg;
+pub mod sqlite;
+
+pub use self::pg::PgRepository;
+pub use self::sqlite::SqliteRepository;
+
+/// Access to all persisted polls, items, proposals, votes and voters.
+///
+/// This is plain data access only. Rules like when a poll may be started or
+/// whether a vote is accepted live in the service modules (`poll`, `vote`, ...).
+pub trait PollRepository {
+    fn create_poll(&self, poll_name: &str, poll_status: PollStatus, creator_id: Option<&str>, channel_id: Option<&str>) -> bool;
+    fn find_poll_by_name(&self, poll_name: &str) -> Option<Poll>;
+    /// The oldest poll whose name folds to `normalized_name`, see `matching::normalize`.
+    fn find_poll_by_normalized_name(&self, normalized_name: &str) -> Option<Poll>;
+    fn find_poll_by_id(&self, poll_id: i32) -> Option<Poll>;
+    /// One page of the polls matching `filter` and the number of all matching polls.
+    fn find_polls_page(&self, filter: &PollFilter, pagination: &Pagination) -> (Vec<Poll>, i64);
+    fn find_polls(&self) -> Vec<Poll>;
+    fn update_poll_status(&self, poll_name: &str, poll_status: PollStatus) -> bool;
+    fn update_poll_option(&self, poll_id: i32, option: &PollOption) -> bool;
+    /// Sets when a poll was started and concluded, e.g. for imported polls.
+    fn update_poll_timestamps(&self, poll_id: i32, started: Option<&str>, concluded: Option<&str>) -> bool;
+
+    /// Invites a voter to a poll. Returns `false` if the voter was already invited.
+    fn insert_poll_participant(&self, poll_id: i32, voter_id: i32) -> bool;
+    fn find_participants_by_poll(&self, poll: &Poll) -> Vec<Voter>;
+
+    /// Deletes a poll with its proposals, votes, invitations and audit entries.
+    fn delete_poll(&self, poll_id: i32) -> bool;
+
+    fn create_item(&self, item_name: &str) -> bool;
+    fn find_item_by_name(&self, item_name: &str) -> Option<Item>;
+    /// The oldest item whose name folds to `normalized_name`, see `matching::normalize`.
+    fn find_item_by_normalized_name(&self, normalized_name: &str) -> Option<Item>;
+    fn find_item_by_id(&self, item_id: i32) -> Option<Item>;
+    /// One page of the items matching `filter` and the number of all matching items.
+    fn find_items_page(&self, filter: &ItemFilter, pagination: &Pagination) -> (Vec<Item>, i64);
+    /// All items matching `filter`, oldest first.
+    fn find_items_matching(&self, filter: &ItemFilter) -> Vec<Item>;
+    fn update_item_option(&self, item_id: i32, option: &ItemOption) -> bool;
+    fn find_items(&self) -> Vec<Item>;
+
+    /// Adds another name for an item. Returns `false` if the alias is already taken.
+    fn insert_item_alias(&self, item_id: i32, alias: &str) -> bool;
+    fn find_item_by_alias(&self, alias: &str) -> Option<Item>;
+    fn find_item_aliases(&self) -> Vec<ItemAlias>;
+
+    fn insert_proposal(&self, poll_id: i32, item_id: i32) -> bool;
+    fn find_proposal_by_id(&self, proposal_id: i32) -> Option<Proposal>;
+    fn find_proposal_by_poll_id_and_item_id(&self, poll_id: i32, item_id: i32) -> Option<Proposal>;
+    fn find_proposals_by_poll(&self, poll: &Poll) -> Vec<Proposal>;
+
+    fn upsert_vote(&self, voter_id: i32, proposal_id: i32, weight: i32) -> bool;
+    fn find_votes_by_proposal(&self, proposal: &Proposal) -> Vec<Vote>;
+    fn find_votes_by_poll_and_voter(&self, poll: &Poll, voter_id: i32) -> Vec<Vote>;
+
+    /// All proposed items of `poll` with their votes and voters, in the order
+    /// the proposals were made. Loaded with a single query.
+    fn find_poll_results(&self, poll: &Poll) -> Vec<(Item, Vec<(Vote, Voter)>)>;
+
+    fn create_voter(&self, user_id: &str, user_name: &str) -> bool;
+    fn find_voter_by_slack_id(&self, slack_id: &str) -> Option<Voter>;
+    fn find_voter_by_id(&self, voter_id: i32) -> Option<Voter>;
+    fn find_voters(&self) -> Vec<Voter>;
+    fn count_voters(&self) -> i64;
+
+    /// Statistics of every item that was proposed in a concluded poll, most wins first.
+    fn find_item_stats(&self) -> Vec<ItemStats>;
+    /// Statistics of every voter, most concluded polls voted in first. Anonymous polls are left out.
+    fn find_voter_stats(&self) -> Vec<VoterStats>;
+    /// The number of voters that took part in each concluded poll.
+    fn count_participants_per_concluded_poll(&self) -> Vec<i64>;
+    /// The results of `item` in the concluded polls, oldest first.
+    fn find_item_trend(&self, item: &Item) -> Vec<ItemTrendPoint>;
+
+    fn insert_audit_entry(&self, entry: &NewAuditEntry) -> bool;
+    /// The audit log of a poll, oldest entry first.
+    fn find_audit_entries_by_poll(&self, poll_id: i32) -> Vec<AuditEntry>;
+
+    fn find_setting(&self, scope: SettingScope, scope_id: &str, name: &str) -> Option<String>;
+    fn set_setting(&self, scope: SettingScope, scope_id: &str, name: &str, value: &str) -> bool;
+
+    fn find_proposal_by_poll_name_and_item_name(&self, poll_name: &str, item_name: &str) -> Option<Proposal> {
+        let poll_option = self.find_poll_by_name(poll_name);
+        let item_option = self.find_item_by_name(item_name);
+
+        match (poll_option, item_option) {
+            (Some(poll), Some(item)) => self.find_proposal_by_poll_id_and_item_id(poll.id, item.id),
+            _ => None
+        }
+    }
+}
+
+/// Connects to the database behind `database_url`, applies pending migrations
+/// and returns the matching repository. `postgres://` URLs select PostgreSQL,
+/// everything else is treated as an SQLite database file.
+pub fn establish(database_url: &str) -> Box<PollRepository> {
+    if is_postgres_url(database_url) {
+        Box::new(PgRepository::establish(database_url))
+    } else {
+        Box::new(SqliteRepository::establish(database_url))
+    }
+}
+
+pub fn is_postgres_url(database_url: &str) -> bool {
+    database_url.starts_with("postgres://") || database_url.starts_with("postgresql://")
+}
@@ -0,0 +1,43 @@
+use diesel;
+use diesel::expression::dsl::{count_star, sql};
+use diesel::prelude::*;
+use diesel::types::{BigInt, Integer, Nullable, Text};
+use diesel::result::DatabaseErrorKind::UniqueViolation;
+use diesel::result::Error::DatabaseError;
+use diesel::pg::PgConnection;
+
+use std::io;
+
+use models::*;
+use repository::PollRepository;
+
+embed_migrations!("migrations/postgres");
+
+pub struct PgRepository {
+    db_conn: PgConnection
+}
+
+impl PgRepository {
+    pub fn new(db_conn: PgConnection) -> PgRepository {
+        PgRepository {
+            db_conn: db_conn
+        }
+    }
+
+    /// Connects to the PostgreSQL server, applies all pending migrations and
+    /// calls `fill_normalized_names`. The database itself has to exist already.
+    pub fn establish(database_url: &str) -> PgRepository {
+        let db_conn = PgConnection::establish(database_url)
+            .expect(&format!("Error connecting to {}", database_url));
+
+        embedded_migrations::run_with_output(&db_conn, &mut io::stdout())
+            .expect("Cannot run database migrations.");
+
+        let repo = PgRepository::new(db_conn);
+        repo.fill_normalized_names();
+
+        repo
+    }
+}
+
+diesel_repository!(PgRepository, ::diesel::pg::Pg);
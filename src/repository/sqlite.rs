@@ -0,0 +1,83 @@
+use diesel;
+use diesel::expression::dsl::{count_star, sql};
+use diesel::prelude::*;
+use diesel::types::{BigInt, Integer, Nullable, Text};
+use diesel::result::DatabaseErrorKind::UniqueViolation;
+use diesel::result::Error::DatabaseError;
+use diesel::sqlite::SqliteConnection;
+
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+
+use models::*;
+use repository::PollRepository;
+
+embed_migrations!("migrations/sqlite");
+
+/// How long a statement waits for a lock held by another connection, e.g. a
+/// backup or the command line, before failing.
+const BUSY_TIMEOUT_MILLIS: u32 = 5000;
+
+pub struct SqliteRepository {
+    db_conn: SqliteConnection
+}
+
+impl SqliteRepository {
+    pub fn new(db_conn: SqliteConnection) -> SqliteRepository {
+        SqliteRepository {
+            db_conn: db_conn
+        }
+    }
+
+    /// Opens (and if necessary creates) the SQLite database file, applies
+    /// all pending migrations and calls `fill_normalized_names`.
+    pub fn establish(database_url: &str) -> SqliteRepository {
+        create_database_file_if_missing(database_url);
+
+        let db_conn = SqliteConnection::establish(database_url)
+            .expect(&format!("Error connecting to {}", database_url));
+
+        db_conn.execute(&format!("PRAGMA busy_timeout = {}", BUSY_TIMEOUT_MILLIS))
+            .expect("Cannot set busy timeout.");
+
+        embedded_migrations::run_with_output(&db_conn, &mut io::stdout())
+            .expect("Cannot run database migrations.");
+
+        let repo = SqliteRepository::new(db_conn);
+        repo.fill_normalized_names();
+
+        repo
+    }
+
+    /// Creates a fresh, fully migrated database that only lives in memory.
+    pub fn in_memory() -> SqliteRepository {
+        SqliteRepository::establish(":memory:")
+    }
+}
+
+diesel_repository!(SqliteRepository, ::diesel::sqlite::Sqlite);
+
+fn create_database_file_if_missing(database_url: &str) {
+    if database_url == ":memory:" {
+        return;
+    }
+
+    let path = Path::new(database_url.trim_left_matches("sqlite://"));
+
+    if path.exists() {
+        return;
+    }
+
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)
+                .expect(&format!("Cannot create directory for database {}", database_url));
+        }
+    }
+
+    File::create(path)
+        .expect(&format!("Cannot create database file {}", database_url));
+
+    println!("[Info] Created database file {}.", path.display());
+}
@@ -1 +1,99 @@
-infer_schema!("dotenv:DATABASE_URL");
+table! {
+    polls {
+        id -> Integer,
+        name -> Text,
+        status -> Text,
+        started_at -> Nullable<Text>,
+        concluded_at -> Nullable<Text>,
+        anonymous -> Bool,
+        visibility -> Text,
+        creator_slack_id -> Nullable<Text>,
+        budget -> Nullable<Integer>,
+        max_approvals -> Nullable<Integer>,
+        eligibility -> Text,
+        channel_id -> Nullable<Text>,
+        quorum -> Nullable<Integer>,
+        auto_conclude -> Text,
+        normalized_name -> Nullable<Text>,
+    }
+}
+
+table! {
+    items {
+        id -> Integer,
+        name -> Text,
+        category -> Nullable<Text>,
+        address -> Nullable<Text>,
+        price_level -> Nullable<Integer>,
+        menu_url -> Nullable<Text>,
+        vegetarian -> Bool,
+        vegan -> Bool,
+        tags -> Text,
+        normalized_name -> Nullable<Text>,
+    }
+}
+
+table! {
+    proposals {
+        id -> Integer,
+        poll_id -> Integer,
+        item_id -> Integer,
+    }
+}
+
+table! {
+    votes {
+        id -> Integer,
+        voter_id -> Integer,
+        proposal_id -> Integer,
+        weight -> Integer,
+    }
+}
+
+table! {
+    voters {
+        id -> Integer,
+        name -> Text,
+        slack_id -> Nullable<Text>,
+    }
+}
+
+table! {
+    settings {
+        id -> Integer,
+        scope -> Text,
+        scope_id -> Text,
+        name -> Text,
+        value -> Text,
+    }
+}
+
+table! {
+    poll_participants {
+        id -> Integer,
+        poll_id -> Integer,
+        voter_id -> Integer,
+    }
+}
+
+table! {
+    item_aliases {
+        id -> Integer,
+        item_id -> Integer,
+        alias -> Text,
+    }
+}
+
+table! {
+    audit_log {
+        id -> Integer,
+        created_at -> Text,
+        slack_id -> Nullable<Text>,
+        voter_id -> Nullable<Integer>,
+        channel_id -> Nullable<Text>,
+        command -> Text,
+        parameters -> Text,
+        outcome -> Text,
+        poll_id -> Nullable<Integer>,
+    }
+}
@@ -0,0 +1,83 @@
+use models::*;
+use repository::PollRepository;
+
+/// Votes a voter needs before they can be the most contrarian voter.
+const MIN_CONTRARIAN_VOTES: i64 = 3;
+
+/// The statistics shown by `!stats`.
+#[derive(Clone, Debug)]
+pub struct Overview {
+    pub concluded_polls: usize,
+    pub top_items: Vec<ItemStats>,
+    /// Average number of voters per concluded poll.
+    pub average_participants: f64,
+    pub voters_registered: i64,
+    pub most_contrarian: Option<VoterStats>
+}
+
+impl Overview {
+    /// Share of the registered voters that took part in an average poll, from 0 to 100.
+    pub fn average_turnout(&self) -> f64 {
+        if self.voters_registered == 0 {
+            0.0
+        } else {
+            (self.average_participants * 100.0 / self.voters_registered as f64).min(100.0)
+        }
+    }
+}
+
+/// Whether an item did better or worse lately.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Trend {
+    Rising,
+    Falling,
+    Steady
+}
+
+pub fn overview(repo: &PollRepository, num_items: usize) -> Overview {
+    let participants = repo.count_participants_per_concluded_poll();
+
+    let average_participants = if participants.is_empty() {
+        0.0
+    } else {
+        participants.iter().sum::<i64>() as f64 / participants.len() as f64
+    };
+
+    Overview {
+        concluded_polls: participants.len(),
+        top_items: repo.find_item_stats().into_iter().filter(|stats| stats.wins > 0).take(num_items).collect(),
+        average_participants: average_participants,
+        voters_registered: repo.count_voters(),
+        most_contrarian: most_contrarian(repo.find_voter_stats())
+    }
+}
+
+/// The voter that voted against the final score most often, relative to their votes.
+pub fn most_contrarian(voter_stats: Vec<VoterStats>) -> Option<VoterStats> {
+    voter_stats.into_iter()
+        .filter(|stats| stats.votes >= MIN_CONTRARIAN_VOTES && stats.contrarian_votes > 0)
+        .fold(None, |most: Option<VoterStats>, stats| match most {
+            Some(ref most) if most.contrarian_rate() >= stats.contrarian_rate() => Some(most.clone()),
+            _ => Some(stats)
+        })
+}
+
+/// Compares the average score of the newer half of the polls with the older half.
+pub fn trend(points: &[ItemTrendPoint]) -> Trend {
+    if points.len() < 2 {
+        return Trend::Steady;
+    }
+
+    let (older, newer) = points.split_at(points.len() / 2);
+    let average = |points: &[ItemTrendPoint]| points.iter().map(|point| point.score).sum::<i64>() as f64 / points.len() as f64;
+
+    let difference = average(newer) - average(older);
+
+    if difference > 0.0 {
+        Trend::Rising
+    } else if difference < 0.0 {
+        Trend::Falling
+    } else {
+        Trend::Steady
+    }
+}
@@ -0,0 +1,117 @@
+use rand::Rng;
+
+use std::collections::HashMap;
+
+use models::*;
+use poll;
+use repository::PollRepository;
+use tally;
+use timestamp;
+
+/// Days after which a place we went to counts as fully "due" again.
+const RECENCY_DAYS: i64 = 14;
+
+/// Only downvotes in polls concluded within this many days lower a place's chances.
+const DOWNVOTE_DAYS: i64 = 30;
+
+/// Even the place we went to yesterday keeps a small chance.
+const MIN_RECENCY: f64 = 0.05;
+
+/// What happened to an item in concluded polls.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ItemHistory {
+    pub wins: usize,
+    /// Days since the item last won a poll.
+    pub days_since_last_win: Option<i64>,
+    pub recent_downvotes: usize
+}
+
+/// An item proposed by `suggest` with the reasons for it.
+#[derive(Clone, Debug)]
+pub struct Suggestion {
+    pub item: Item,
+    pub history: ItemHistory,
+    pub weight: f64
+}
+
+/// Collects the history of every item from the concluded polls.
+pub fn item_histories(repo: &PollRepository) -> HashMap<i32, ItemHistory> {
+    let mut histories: HashMap<i32, ItemHistory> = HashMap::new();
+
+    for concluded_poll in repo.find_polls().iter().filter(|poll| poll::is_concluded(poll)) {
+        let days_since_conclusion = concluded_poll.concluded_at.as_ref().and_then(|concluded_at| timestamp::days_since(concluded_at));
+        let tally = tally::tally_votes(repo, concluded_poll);
+
+        if let Some(winner) = tally.winner() {
+            let history = histories.entry(winner.item.id).or_insert_with(ItemHistory::default);
+            history.wins += 1;
+
+            if let Some(days) = days_since_conclusion {
+                history.days_since_last_win = Some(history.days_since_last_win.map_or(days, |last_win| last_win.min(days)));
+            }
+        }
+
+        if days_since_conclusion.map_or(false, |days| days <= DOWNVOTE_DAYS) {
+            for proposal in tally.proposals.iter() {
+                histories.entry(proposal.item.id).or_insert_with(ItemHistory::default).recent_downvotes += proposal.negative;
+            }
+        }
+    }
+
+    histories
+}
+
+/// How likely an item is suggested: places that often win are preferred,
+/// places we went to recently or that were downvoted lately less so.
+pub fn weight(history: &ItemHistory) -> f64 {
+    let popularity = 1.0 + history.wins as f64;
+
+    let recency = match history.days_since_last_win {
+        Some(days) => (days.min(RECENCY_DAYS) as f64 / RECENCY_DAYS as f64).max(MIN_RECENCY),
+        None => 1.0
+    };
+
+    let penalty = 1.0 / (1.0 + history.recent_downvotes as f64);
+
+    popularity * recency * penalty
+}
+
+/// Picks up to `count` different items at random, each with a chance
+/// proportional to its weight.
+pub fn suggest<R: Rng>(repo: &PollRepository, items: Vec<Item>, count: usize, rng: &mut R) -> Vec<Suggestion> {
+    let histories = item_histories(repo);
+
+    let mut candidates: Vec<Suggestion> = items.into_iter()
+        .map(|item| {
+            let history = histories.get(&item.id).cloned().unwrap_or_default();
+
+            Suggestion {
+                weight: weight(&history),
+                history: history,
+                item: item
+            }
+        })
+        .collect();
+
+    let mut suggestions = Vec::new();
+
+    while suggestions.len() < count && !candidates.is_empty() {
+        let total: f64 = candidates.iter().map(|candidate| candidate.weight).sum();
+        let mut target = rng.gen::<f64>() * total;
+
+        let mut index = candidates.len() - 1;
+
+        for (candidate_index, candidate) in candidates.iter().enumerate() {
+            if target < candidate.weight {
+                index = candidate_index;
+                break;
+            }
+
+            target -= candidate.weight;
+        }
+
+        suggestions.push(candidates.remove(index));
+    }
+
+    suggestions
+}
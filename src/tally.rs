@@ -0,0 +1,141 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use models::*;
+use poll;
+use repository::PollRepository;
+use transport::Transport;
+
+/// Everything we know about one proposal of a poll after counting the votes.
+#[derive(Clone, Debug)]
+pub struct ProposalTally {
+    pub item: Item,
+    pub votes: Vec<(Vote, Voter)>,
+    /// Sum of all vote weights.
+    pub score: i32,
+    pub positive: usize,
+    pub negative: usize,
+    /// Share of the participating voters that voted in favour, from 0 to 100.
+    pub percentage: f64,
+    /// 1 for the best proposal. Proposals with equal score and votes share a rank.
+    pub rank: usize
+}
+
+/// The counted votes of a poll, best proposal first.
+#[derive(Clone, Debug)]
+pub struct Tally {
+    pub proposals: Vec<ProposalTally>,
+    /// Number of voters that voted for at least one proposal.
+    pub voters_voted: usize,
+    /// Voters that may vote in the poll, see `poll::expected_voter_ids`.
+    pub voters_eligible: usize,
+    /// Voters that have to take part for the poll to have a winner.
+    pub quorum: Option<usize>
+}
+
+impl Tally {
+    /// Share of the eligible voters that took part, from 0 to 100.
+    pub fn turnout(&self) -> f64 {
+        percentage(self.voters_voted, self.voters_eligible)
+    }
+
+    pub fn has_quorum(&self) -> bool {
+        self.quorum.map_or(true, |quorum| self.voters_voted >= quorum)
+    }
+
+    pub fn has_voted(&self, voter_id: i32) -> bool {
+        self.proposals.iter()
+            .flat_map(|proposal| proposal.votes.iter())
+            .any(|&(ref vote, _)| vote.voter_id == voter_id)
+    }
+
+    /// The best proposal, if it has a positive score, no other proposal shares
+    /// its rank and the quorum is reached.
+    pub fn winner(&self) -> Option<&ProposalTally> {
+        if !self.has_quorum() {
+            return None;
+        }
+
+        let mut top = self.proposals.iter().take_while(|proposal| proposal.rank == 1);
+
+        match (top.next(), top.next()) {
+            (Some(winner), None) if winner.score > 0 => Some(winner),
+            _ => None
+        }
+    }
+}
+
+/// Counts the votes of `poll`. The turnout is measured against the voters
+/// eligible for it, or all registered voters if they cannot be determined.
+pub fn tally_poll(repo: &PollRepository, transport: &Transport, poll: &Poll) -> Tally {
+    let voters_eligible = poll::expected_voter_ids(repo, transport, poll)
+        .map_or_else(|| repo.count_voters() as usize, |voter_ids| voter_ids.len());
+
+    let mut tally = tally_votes(repo, poll);
+    tally.voters_eligible = voters_eligible.max(tally.voters_voted);
+
+    tally
+}
+
+/// Counts the votes of `poll` without looking up who may vote, for when only
+/// the proposals and the winner matter. The turnout is always 100%.
+pub fn tally_votes(repo: &PollRepository, poll: &Poll) -> Tally {
+    let mut tally = tally(repo.find_poll_results(poll), 0);
+    tally.quorum = poll.quorum.map(|quorum| quorum as usize);
+
+    tally
+}
+
+pub fn tally(results: Vec<(Item, Vec<(Vote, Voter)>)>, voters_eligible: usize) -> Tally {
+    let voters_voted = results.iter()
+        .flat_map(|&(_, ref votes)| votes.iter().map(|&(ref vote, _)| vote.voter_id))
+        .collect::<HashSet<_>>()
+        .len();
+
+    let mut proposals: Vec<_> = results.into_iter()
+        .map(|(item, votes)| {
+            let positive = votes.iter().filter(|&&(ref vote, _)| vote.weight > 0).count();
+            let negative = votes.iter().filter(|&&(ref vote, _)| vote.weight < 0).count();
+
+            ProposalTally {
+                score: votes.iter().map(|&(ref vote, _)| vote.weight).sum(),
+                positive: positive,
+                negative: negative,
+                percentage: percentage(positive, voters_voted),
+                rank: 0,
+                item: item,
+                votes: votes
+            }
+        })
+        .collect();
+
+    proposals.sort_by(|a, b| compare_proposals(a, b).then_with(|| a.item.name.cmp(&b.item.name)));
+
+    for index in 0..proposals.len() {
+        proposals[index].rank = if index > 0 && compare_proposals(&proposals[index - 1], &proposals[index]) == Ordering::Equal {
+            proposals[index - 1].rank
+        } else {
+            index + 1
+        };
+    }
+
+    Tally {
+        proposals: proposals,
+        voters_voted: voters_voted,
+        voters_eligible: voters_eligible.max(voters_voted),
+        quorum: None
+    }
+}
+
+// Higher score first, more votes in favour break ties.
+fn compare_proposals(a: &ProposalTally, b: &ProposalTally) -> Ordering {
+    b.score.cmp(&a.score).then(b.positive.cmp(&a.positive))
+}
+
+fn percentage(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        (part as f64) * 100.0 / (total as f64)
+    }
+}
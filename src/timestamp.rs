@@ -0,0 +1,18 @@
+use chrono::{NaiveDateTime, Utc};
+
+/// How points in time are stored in the database, e.g. `2017-09-02 12:30:00` (UTC).
+/// Timestamps in this format sort correctly as text.
+pub const FORMAT: &'static str = "%Y-%m-%d %H:%M:%S";
+
+pub fn now() -> String {
+    Utc::now().naive_utc().format(FORMAT).to_string()
+}
+
+pub fn parse(timestamp: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(timestamp, FORMAT).ok()
+}
+
+/// Whole days that passed since `timestamp`, `None` if it cannot be parsed.
+pub fn days_since(timestamp: &str) -> Option<i64> {
+    parse(timestamp).map(|then| Utc::now().naive_utc().signed_duration_since(then).num_days())
+}
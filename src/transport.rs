@@ -0,0 +1,173 @@
+use slack::RtmClient;
+use slack::api;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use render::RichMessage;
+
+/// Something commands can send their replies through.
+pub trait Transport {
+    fn send_message(&self, channel_id: &str, message: &str);
+
+    /// Sends a formatted message. Transports without formatting send the plain text version.
+    fn send_rich_message(&self, channel_id: &str, message: &RichMessage) {
+        self.send_message(channel_id, &message.to_plain_text());
+    }
+
+    /// The Slack ids of the members of a channel, `None` if they cannot be determined.
+    fn channel_members(&self, channel_id: &str) -> Option<Vec<String>>;
+
+    /// Shares a file in a channel. Returns `false` on failure. Without Slack,
+    /// `slackbot-rs export` writes exports to disk instead.
+    fn upload_file(&self, channel_id: &str, file_name: &str, content: &str) -> bool;
+}
+
+/// Sends plain messages over the real time connection and formatted ones
+/// through the web API, which is the only way to post attachments.
+pub struct SlackTransport<'a> {
+    cli: &'a RtmClient,
+    api_token: &'a str
+}
+
+impl <'a> SlackTransport<'a> {
+    pub fn new(cli: &'a RtmClient, api_token: &'a str) -> SlackTransport<'a> {
+        SlackTransport {
+            cli: cli,
+            api_token: api_token
+        }
+    }
+}
+
+impl <'a> Transport for SlackTransport<'a> {
+    fn send_message(&self, channel_id: &str, message: &str) {
+        let _ = self.cli.sender().send_message(channel_id, message);
+    }
+
+    fn send_rich_message(&self, channel_id: &str, message: &RichMessage) {
+        let client = match api::requests::default_client() {
+            Ok(client) => client,
+            Err(err) => {
+                println!("[Warn] Cannot create web API client, falling back to plain text: {:?}", err);
+                return self.send_message(channel_id, &message.to_plain_text());
+            }
+        };
+
+        let attachments = message.attachments_json().to_string();
+
+        let request = api::chat::PostMessageRequest {
+            channel: channel_id,
+            text: &message.text,
+            attachments: Some(&attachments),
+            as_user: Some(true),
+            ..Default::default()
+        };
+
+        if let Err(err) = api::chat::post_message(&client, self.api_token, &request) {
+            println!("[Warn] Cannot post formatted message, falling back to plain text: {:?}", err);
+            self.send_message(channel_id, &message.to_plain_text());
+        }
+    }
+
+    fn channel_members(&self, channel_id: &str) -> Option<Vec<String>> {
+        let client = match api::requests::default_client() {
+            Ok(client) => client,
+            Err(err) => {
+                println!("[Warn] Cannot create web API client: {:?}", err);
+                return None;
+            }
+        };
+
+        // Private channels are called groups in the web API
+        let members = if channel_id.starts_with("G") {
+            api::groups::info(&client, self.api_token, &api::groups::InfoRequest { channel: channel_id })
+                .map(|response| response.group.and_then(|group| group.members))
+                .map_err(|err| format!("{:?}", err))
+        } else {
+            api::channels::info(&client, self.api_token, &api::channels::InfoRequest { channel: channel_id })
+                .map(|response| response.channel.and_then(|channel| channel.members))
+                .map_err(|err| format!("{:?}", err))
+        };
+
+        match members {
+            Ok(members) => members,
+            Err(err) => {
+                println!("[Warn] Cannot load members of channel {}: {}", channel_id, err);
+                None
+            }
+        }
+    }
+
+    fn upload_file(&self, channel_id: &str, file_name: &str, content: &str) -> bool {
+        let client = match api::requests::default_client() {
+            Ok(client) => client,
+            Err(err) => {
+                println!("[Warn] Cannot create web API client: {:?}", err);
+                return false;
+            }
+        };
+
+        let request = api::files::UploadRequest {
+            content: Some(content),
+            filename: Some(file_name),
+            title: Some(file_name),
+            channels: Some(channel_id),
+            ..Default::default()
+        };
+
+        match api::files::upload(&client, self.api_token, &request) {
+            Ok(_) => true,
+            Err(err) => {
+                println!("[Warn] Cannot upload {}: {:?}", file_name, err);
+                false
+            }
+        }
+    }
+}
+
+/// Transport that only remembers what has been sent. Used to test commands
+/// without a Slack connection.
+pub struct RecordingTransport {
+    messages: RefCell<Vec<(String, String)>>,
+    members: RefCell<HashMap<String, Vec<String>>>,
+    uploads: RefCell<Vec<(String, String, String)>>
+}
+
+impl RecordingTransport {
+    pub fn new() -> RecordingTransport {
+        RecordingTransport {
+            messages: RefCell::new(Vec::new()),
+            members: RefCell::new(HashMap::new()),
+            uploads: RefCell::new(Vec::new())
+        }
+    }
+
+    pub fn set_channel_members(&self, channel_id: &str, members: &[&str]) {
+        self.members.borrow_mut().insert(channel_id.to_owned(), members.iter().map(|member| member.to_string()).collect());
+    }
+
+    /// Returns and forgets all messages sent so far as (channel, message) pairs.
+    pub fn take_messages(&self) -> Vec<(String, String)> {
+        self.messages.borrow_mut().drain(..).collect()
+    }
+
+    /// Returns and forgets all files uploaded so far as (channel, file name, content) triples.
+    pub fn take_uploads(&self) -> Vec<(String, String, String)> {
+        self.uploads.borrow_mut().drain(..).collect()
+    }
+}
+
+impl Transport for RecordingTransport {
+    fn send_message(&self, channel_id: &str, message: &str) {
+        self.messages.borrow_mut().push((channel_id.to_owned(), message.to_owned()));
+    }
+
+    fn channel_members(&self, channel_id: &str) -> Option<Vec<String>> {
+        self.members.borrow().get(channel_id).cloned()
+    }
+
+    fn upload_file(&self, channel_id: &str, file_name: &str, content: &str) -> bool {
+        self.uploads.borrow_mut().push((channel_id.to_owned(), file_name.to_owned(), content.to_owned()));
+        true
+    }
+}
@@ -0,0 +1,142 @@
+use models::*;
+use poll;
+use repository::PollRepository;
+use transport::Transport;
+
+pub fn create_proposal(repo: &PollRepository, poll: &Poll, item: &Item) -> bool {
+    if !repo.find_poll_by_id(poll.id).is_some() {
+        return false;
+    }
+
+    if !repo.find_item_by_id(item.id).is_some() {
+        return false;
+    }
+
+    repo.insert_proposal(poll.id, item.id)
+}
+
+/// Returns the proposal of `item` in `poll` and creates it if necessary.
+pub fn find_or_create_proposal(repo: &PollRepository, poll: &Poll, item: &Item) -> Option<Proposal> {
+    if let Some(proposal) = repo.find_proposal_by_poll_id_and_item_id(poll.id, item.id) {
+        return Some(proposal);
+    }
+
+    // Somebody else may have created the proposal concurrently, so look it up either way
+    create_proposal(repo, poll, item);
+    repo.find_proposal_by_poll_id_and_item_id(poll.id, item.id)
+}
+
+/// Whether `voter` may vote in `poll` at all. Members of the poll's channel
+/// are looked up through the transport.
+pub fn is_eligible(repo: &PollRepository, transport: &Transport, poll: &Poll, voter: &Voter) -> bool {
+    match PollEligibility::from_str(poll.eligibility.as_str()) {
+        Some(PollEligibility::Channel) => match (poll.channel_id.as_ref(), voter.slack_id.as_ref()) {
+            (Some(channel_id), Some(slack_id)) => transport.channel_members(channel_id)
+                .map_or(false, |members| members.contains(slack_id)),
+            // Polls from before we kept track of their channel
+            (None, _) => true,
+            _ => false
+        },
+        Some(PollEligibility::Invited) => repo.find_participants_by_poll(poll)
+            .iter()
+            .any(|participant| participant.id == voter.id),
+        _ => true
+    }
+}
+
+pub fn can_vote_for_proposal(repo: &PollRepository, transport: &Transport, proposal: &Proposal, voter: &Voter) -> bool {
+    match repo.find_poll_by_id(proposal.poll_id) {
+        Some(poll) => poll::is_in_progress(&poll) && is_eligible(repo, transport, &poll, voter),
+        None => false
+    }
+}
+
+/// The most points a single vote can weigh, in either direction.
+pub const MAX_WEIGHT: i32 = 1000;
+
+/// What a voter has left to spend in a poll. `None` means there is no limit.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Budget {
+    pub points: Option<i32>,
+    pub approvals: Option<i32>
+}
+
+impl Budget {
+    pub fn allows(&self, weight: i32) -> bool {
+        let enough_points = self.points.map_or(true, |points| weight.abs() <= points);
+        let enough_approvals = weight <= 0 || self.approvals.map_or(true, |approvals| approvals > 0);
+
+        enough_points && enough_approvals
+    }
+}
+
+/// The budget the voter has left in `poll`, not counting the vote for the
+/// proposal `except_proposal_id` as that one would be replaced.
+pub fn remaining_budget(repo: &PollRepository, poll: &Poll, voter_id: i32, except_proposal_id: Option<i32>) -> Budget {
+    let votes: Vec<_> = repo.find_votes_by_poll_and_voter(poll, voter_id)
+        .into_iter()
+        .filter(|vote| Some(vote.proposal_id) != except_proposal_id)
+        .collect();
+
+    let spent_points: i32 = votes.iter().map(|vote| vote.weight.abs()).sum();
+    let approvals = votes.iter().filter(|vote| vote.weight > 0).count() as i32;
+
+    Budget {
+        points: poll.budget.map(|budget| (budget - spent_points).max(0)),
+        approvals: poll.max_approvals.map(|max_approvals| (max_approvals - approvals).max(0))
+    }
+}
+
+/// Votes weigh more than one point only in polls with a budget to distribute.
+pub fn effective_weight(poll: &Poll, weight: i32) -> i32 {
+    if poll.budget.is_some() {
+        weight
+    } else {
+        weight.signum()
+    }
+}
+
+/// Sets the vote of a voter for a proposal, replacing any earlier vote.
+/// Votes exceeding the voter's budget are rejected.
+pub fn set_vote(repo: &PollRepository, transport: &Transport, voter_id: i32, proposal_id: i32, weight: i32) -> bool {
+    let voter_option = repo.find_voter_by_id(voter_id);
+
+    if !voter_option.is_some() {
+        return false;
+    }
+
+    let voter = voter_option.unwrap();
+
+    let proposal_option = repo.find_proposal_by_id(proposal_id);
+
+    if !proposal_option.is_some() {
+        return false;
+    }
+
+    let proposal = proposal_option.unwrap();
+
+    if !can_vote_for_proposal(repo, transport, &proposal, &voter) {
+        return false;
+    }
+
+    let poll = repo.find_poll_by_id(proposal.poll_id).unwrap();
+
+    if !remaining_budget(repo, &poll, voter_id, Some(proposal_id)).allows(weight) {
+        return false;
+    }
+
+    repo.upsert_vote(voter_id, proposal_id, weight)
+}
+
+/// `+` and `-` count as one point, numbers like `3` or `-2` as that many.
+/// `None` for zero, numbers beyond `MAX_WEIGHT` and anything else.
+pub fn parse_weight(weight_char: &str) -> Option<i32> {
+    match weight_char {
+        "+" => Some(1),
+        "-" => Some(-1),
+        _ => match weight_char.parse::<i32>() {
+            Ok(weight) if weight != 0 && weight >= -MAX_WEIGHT && weight <= MAX_WEIGHT => Some(weight),
+            _ => None
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use slack::User;
+
+use models::*;
+use repository::PollRepository;
+
+/// Registers the Slack user as a voter. Returns `false` if the user is
+/// already registered.
+pub fn create_voter(repo: &PollRepository, user_id: &str, user_name: &str) -> bool {
+    repo.create_voter(user_id, user_name)
+}
+
+pub fn find_voter_by_user(repo: &PollRepository, user: &User) -> Option<Voter> {
+    user.id.as_ref().and_then(|user_id| repo.find_voter_by_slack_id(user_id))
+}
+
+/// The Slack id in a mention like `<@U123>` or `<@U123|alice>`.
+pub fn parse_mention(mention: &str) -> Option<&str> {
+    if !mention.starts_with("<@") || !mention.ends_with(">") {
+        return None;
+    }
+
+    mention[2..mention.len() - 1].split('|').next()
+}
@@ -0,0 +1,894 @@
+extern crate diesel;
+extern crate rand;
+extern crate serde_json;
+extern crate slack;
+extern crate slackbot_rs;
+
+use diesel::Connection;
+use diesel::sqlite::SqliteConnection;
+use rand::{SeedableRng, StdRng};
+use slack::User;
+
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use slackbot_rs::backup;
+use slackbot_rs::command::{Command, Context, get_command, get_command_implementation, get_command_line, get_command_parameters};
+use slackbot_rs::commands::create_commands;
+use slackbot_rs::import;
+use slackbot_rs::item;
+use slackbot_rs::personality::Personalities;
+use slackbot_rs::repository::{PollRepository, SqliteRepository};
+use slackbot_rs::suggest::{self, ItemHistory};
+use slackbot_rs::transport::RecordingTransport;
+
+const CHANNEL: &'static str = "C0LUNCH";
+const DIRECT_CHANNEL: &'static str = "D0ALICE";
+
+struct TestBot {
+    repo: SqliteRepository,
+    transport: RecordingTransport,
+    commands: HashSet<Command>,
+    personalities: Personalities,
+    admins: Vec<String>
+}
+
+impl TestBot {
+    fn new() -> TestBot {
+        TestBot::with_database(":memory:")
+    }
+
+    fn with_database(database_url: &str) -> TestBot {
+        TestBot {
+            repo: SqliteRepository::establish(database_url),
+            transport: RecordingTransport::new(),
+            commands: create_commands(),
+            personalities: Personalities::builtin(Some(42)),
+            admins: Vec::new()
+        }
+    }
+
+    fn with_admins(mut self, admins: &[&str]) -> TestBot {
+        self.admins = admins.iter().map(|admin| admin.to_string()).collect();
+        self
+    }
+
+    /// Runs `input` as if `user` had posted it to the test channel and
+    /// returns all replies of the bot.
+    fn run(&self, user: &Option<User>, input: &str) -> Vec<String> {
+        self.run_in(CHANNEL, user, input)
+    }
+
+    fn run_in(&self, channel_id: &str, user: &Option<User>, input: &str) -> Vec<String> {
+        assert!(self.invoke_in(channel_id, user, input), "Not enough parameters for '{}'.", input);
+
+        self.transport.take_messages()
+            .into_iter()
+            .map(|(channel, message)| {
+                assert_eq!(channel, channel_id);
+                message
+            })
+            .collect()
+    }
+
+    /// Runs `input` like `run_in` and returns whether it had enough parameters,
+    /// leaving the replies to the caller.
+    fn invoke_in(&self, channel_id: &str, user: &Option<User>, input: &str) -> bool {
+        let command_line = get_command_line(input).expect("Input is not a command.");
+        let command_name = get_command(&command_line).expect("Input has no command name.");
+        let command_parameters = get_command_parameters(&command_line);
+
+        let command = get_command_implementation(&command_name, &self.commands)
+            .expect("Unknown command.");
+
+        let channel = Some(channel_id.to_owned());
+        let mut context = Context::new(&self.repo, &self.transport, &channel, user, &self.personalities)
+            .with_input(input)
+            .with_admins(&self.admins);
+
+        command.invoke(&mut context, command_parameters.iter().map(String::as_str).collect())
+    }
+
+    /// Files uploaded by the bot since the last call, as (file name, content) pairs.
+    fn uploads(&self) -> Vec<(String, String)> {
+        self.transport.take_uploads()
+            .into_iter()
+            .map(|(_, file_name, content)| (file_name, content))
+            .collect()
+    }
+
+    fn run_single(&self, user: &Option<User>, input: &str) -> String {
+        let mut replies = self.run(user, input);
+
+        assert_eq!(replies.len(), 1, "Expected exactly one reply to '{}'.", input);
+        replies.remove(0)
+    }
+}
+
+fn user(id: &str, name: &str) -> Option<User> {
+    let json = format!(r#"{{"id": "{}", "name": "{}"}}"#, id, name);
+
+    Some(serde_json::from_str(&json).expect("Cannot create user."))
+}
+
+/// A fresh directory for files written by a test, removed by the caller.
+fn temp_dir(name: &str) -> PathBuf {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().subsec_nanos();
+    let dir = env::temp_dir().join(format!("slackbot-rs-{}-{}", name, nanos));
+
+    fs::create_dir_all(&dir).expect("Cannot create temporary directory.");
+    dir
+}
+
+fn bot_with_running_poll(voters: &[&Option<User>]) -> TestBot {
+    let bot = TestBot::new();
+    let alice = user("U1", "alice");
+
+    bot.run(&alice, "!new_poll mittag");
+    bot.run(&alice, "!new_item curry");
+    bot.run(&alice, "!new_item pizza");
+    bot.run(&alice, "!start_poll mittag");
+
+    for voter in voters {
+        bot.run(voter, "!new_voter");
+    }
+
+    bot
+}
+
+#[test]
+fn vote_is_counted_for_running_poll() {
+    let alice = user("U1", "alice");
+    let bot = bot_with_running_poll(&[&alice]);
+
+    let reply = bot.run_single(&alice, "!vote mittag curry +");
+    assert!(reply.starts_with("Stimme gezählt für 'mittag' bei 'curry' mit Gewichtung 1."), reply);
+
+    let results = bot.run_single(&alice, "!show_poll_results mittag");
+    assert_eq!(results, "Hier die Umfrageergebnisse für mittag:\nBeteiligung: 1 von 1 Wähler (100%)\n1. curry (+1, 100%): alice(1)\n");
+}
+
+#[test]
+fn voting_again_replaces_the_previous_vote() {
+    let alice = user("U1", "alice");
+    let bob = user("U2", "bob");
+    let bot = bot_with_running_poll(&[&alice, &bob]);
+
+    bot.run(&alice, "!vote mittag curry +");
+    bot.run(&bob, "!vote mittag curry +");
+    bot.run(&alice, "!vote mittag curry -");
+
+    let results = bot.run_single(&alice, "!show_poll_results mittag");
+    assert_eq!(results, "Hier die Umfrageergebnisse für mittag:\nBeteiligung: 2 von 2 Wählern (100%)\n1. curry (+0, 50%): alice(-1) bob(1)\n");
+}
+
+#[test]
+fn results_are_ranked_by_score() {
+    let alice = user("U1", "alice");
+    let bob = user("U2", "bob");
+    let carol = user("U3", "carol");
+    let bot = bot_with_running_poll(&[&alice, &bob, &carol]);
+
+    bot.run(&alice, "!vote mittag curry +");
+    bot.run(&alice, "!vote mittag pizza +");
+    bot.run(&bob, "!vote mittag pizza +");
+
+    let results = bot.run_single(&alice, "!show_poll_results mittag");
+    assert_eq!(results, "Hier die Umfrageergebnisse für mittag:\nBeteiligung: 2 von 3 Wählern (67%)\n1. pizza (+2, 100%): alice(1) bob(1)\n2. curry (+1, 50%): alice(1)\n");
+
+    let replies = bot.run(&alice, "!conclude_poll mittag");
+    assert_eq!(replies.len(), 2);
+    assert_eq!(replies[1], ":trophy: 'pizza' gewinnt die Umfrage 'mittag' mit +2 (100% Zustimmung).");
+}
+
+#[test]
+fn vote_is_rejected_for_stopped_poll() {
+    let alice = user("U1", "alice");
+    let bot = TestBot::new();
+
+    bot.run(&alice, "!new_poll mittag");
+    bot.run(&alice, "!new_item curry");
+    bot.run(&alice, "!new_voter");
+
+    let reply = bot.run_single(&alice, "!vote mittag curry +");
+    assert!(reply.starts_with("Ich kann die Stimme für 'mittag' bei 'curry' nicht akzeptieren!"), reply);
+}
+
+#[test]
+fn vote_is_rejected_for_unknown_voter() {
+    let alice = user("U1", "alice");
+    let bot = bot_with_running_poll(&[]);
+
+    let reply = bot.run_single(&alice, "!vote mittag curry +");
+    assert!(reply.starts_with("Ich kenne keinen Wähler 'alice'."), reply);
+}
+
+#[test]
+fn results_of_unknown_poll_are_not_shown() {
+    let alice = user("U1", "alice");
+    let bot = TestBot::new();
+
+    let reply = bot.run_single(&alice, "!show_poll_results abendessen");
+    assert!(reply.starts_with("Ich kann keine Umfrageergebnisse für 'abendessen' anzeigen"), reply);
+}
+
+#[test]
+fn replies_in_the_locale_chosen_by_the_user() {
+    let alice = user("U1", "alice");
+    let bob = user("U2", "bob");
+    let bot = TestBot::new();
+
+    let reply = bot.run_single(&alice, "!locale en");
+    assert!(reply.starts_with("From now on I'll speak English with you."), reply);
+
+    let reply = bot.run_single(&alice, "!new_poll mittag");
+    assert!(reply.starts_with("Created poll 'mittag'."), reply);
+
+    let reply = bot.run_single(&bob, "!new_item curry");
+    assert!(reply.starts_with("Ort 'curry' angelegt."), reply);
+
+    for scope in &["channel", "workspace"] {
+        let reply = bot.run_single(&bob, &format!("!locale en {}", scope));
+        assert!(reply.starts_with("Nur Admins dürfen die Sprache eines Kanals oder aller ändern!"), reply);
+    }
+
+    let bot = bot.with_admins(&["U2"]);
+
+    let reply = bot.run_single(&bob, "!locale en channel");
+    assert!(reply.starts_with("From now on I'll speak English with this channel."), reply);
+}
+
+#[test]
+fn serious_personality_answers_without_remarks() {
+    let alice = user("U1", "alice");
+    let bot = TestBot::new();
+
+    bot.run(&alice, "!personality serious");
+
+    let reply = bot.run_single(&alice, "!new_poll mittag");
+    assert_eq!(reply, "Umfrage 'mittag' angelegt.");
+
+    let reply = bot.run_single(&alice, "!personality serious workspace");
+    assert!(reply.starts_with("Nur Admins dürfen die Persönlichkeit für alle Kanäle ändern!"), reply);
+
+    let bot = bot.with_admins(&["U1"]);
+
+    let reply = bot.run_single(&alice, "!personality serious workspace");
+    assert_eq!(reply, "Ich bin ab jetzt überall 'serious', wo nichts anderes eingestellt ist.");
+}
+
+#[test]
+fn anonymous_poll_accepts_votes_by_direct_message_only() {
+    let alice = user("U1", "alice");
+    let bot = TestBot::new();
+
+    bot.run(&alice, "!new_poll mittag");
+    bot.run(&alice, "!new_item curry");
+    bot.run(&alice, "!new_voter");
+
+    let reply = bot.run_single(&alice, "!poll_set mittag anonymous=ja");
+    assert!(reply.starts_with("Einstellung 'anonymous' der Umfrage 'mittag' ist jetzt 'true'."), reply);
+
+    bot.run(&alice, "!start_poll mittag");
+
+    let reply = bot.run_single(&alice, "!vote mittag curry +");
+    assert!(reply.starts_with("Die Umfrage 'mittag' ist geheim."), reply);
+
+    let replies = bot.run_in(DIRECT_CHANNEL, &alice, "!vote mittag curry +");
+    assert!(replies[0].starts_with("Stimme gezählt"), replies[0].clone());
+
+    let results = bot.run_single(&alice, "!show_poll_results mittag");
+    assert_eq!(results, "Hier die Umfrageergebnisse für mittag:\nGeheime Umfrage: Einzelne Stimmen zeige ich nicht an.\nBeteiligung: 1 von 1 Wähler (100%)\n1. curry (+1, 100%):\n");
+
+    let reply = bot.run_single(&alice, "!poll_set mittag anonymous=nein");
+    assert!(reply.starts_with("Die Einstellungen der Umfrage 'mittag' kann ich nur ändern"), reply);
+}
+
+#[test]
+fn poll_details_show_settings_standings_and_own_vote() {
+    let alice = user("U1", "alice");
+    let bob = user("U2", "bob");
+    let bot = bot_with_running_poll(&[&alice, &bob]);
+
+    bot.run(&alice, "!vote mittag curry +");
+
+    let details = bot.run_single(&alice, "!poll mittag");
+    assert!(details.starts_with(&format!("Umfrage 'mittag': :large_blue_circle: läuft\nAngelegt von <@U1>\nKanal: <#{}>\nAbstimmung mit + und -\nAbstimmen dürfen alle registrierten Wähler.\nGestartet: ", CHANNEL)), details.clone());
+    assert!(details.ends_with(" UTC\n2 Vorschläge:\n1. curry (+1, 100%)\n2. pizza (+0, 0%)\nBeteiligung: 1 von 2 Wählern (50%)\nDu hast abgestimmt.\n"), details.clone());
+
+    let details = bot.run_single(&bob, "!poll mittag");
+    assert!(details.ends_with("Du hast noch nicht abgestimmt.\n"), details);
+}
+
+#[test]
+fn results_are_hidden_until_the_user_voted() {
+    let alice = user("U1", "alice");
+    let bob = user("U2", "bob");
+    let bot = TestBot::new();
+
+    bot.run(&alice, "!new_poll mittag");
+    bot.run(&alice, "!new_item curry");
+    bot.run(&alice, "!poll_set mittag visibility=after-vote");
+    bot.run(&alice, "!start_poll mittag");
+    bot.run(&bob, "!new_voter");
+
+    let reply = bot.run_single(&bob, "!show_poll_results mittag");
+    assert!(reply.starts_with("Die Ergebnisse der Umfrage 'mittag' zeige ich dir erst, wenn du abgestimmt hast."), reply);
+
+    let results = bot.run_single(&alice, "!show_poll_results mittag");
+    assert!(results.starts_with("Hier die Umfrageergebnisse für mittag:"), results);
+
+    bot.run(&bob, "!vote mittag curry +");
+
+    let results = bot.run_single(&bob, "!show_poll_results mittag");
+    assert!(results.starts_with("Hier die Umfrageergebnisse für mittag:"), results);
+}
+
+#[test]
+fn votes_are_limited_by_the_budget() {
+    let alice = user("U1", "alice");
+    let bot = TestBot::new();
+
+    bot.run(&alice, "!new_poll mittag");
+    bot.run(&alice, "!new_item curry");
+    bot.run(&alice, "!new_item pizza");
+    bot.run(&alice, "!poll_set mittag budget=3");
+    bot.run(&alice, "!start_poll mittag");
+    bot.run(&alice, "!new_voter");
+
+    let reply = bot.run_single(&alice, "!vote mittag curry 2");
+    assert!(reply.starts_with("Stimme gezählt für 'mittag' bei 'curry' mit Gewichtung 2. Du hast noch 1 Punkt übrig."), reply);
+
+    let reply = bot.run_single(&alice, "!vote mittag pizza 2");
+    assert!(reply.starts_with("Die Stimme für 'mittag' bei 'pizza' übersteigt dein Budget! Du hast noch 1 Punkt übrig."), reply);
+
+    // Replacing a vote gives its points back first
+    let reply = bot.run_single(&alice, "!vote mittag curry 3");
+    assert!(reply.starts_with("Stimme gezählt für 'mittag' bei 'curry' mit Gewichtung 3. Du hast noch 0 Punkte übrig."), reply);
+
+    for weight in &["abc", "0", "-2147483648", "1001"] {
+        let reply = bot.run_single(&alice, &format!("!vote mittag pizza {}", weight));
+        assert!(reply.starts_with(&format!("Ich verstehe die Gewichtung '{}' nicht.", weight)), reply);
+    }
+
+    let poll = bot.repo.find_poll_by_name("mittag").unwrap();
+    let voter = bot.repo.find_voter_by_slack_id("U1").unwrap();
+    let weights: Vec<i32> = bot.repo.find_votes_by_poll_and_voter(&poll, voter.id).iter().map(|vote| vote.weight).collect();
+    assert_eq!(weights, vec![3]);
+}
+
+#[test]
+fn only_invited_voters_may_vote() {
+    let alice = user("U1", "alice");
+    let bob = user("U2", "bob");
+    let bot = TestBot::new();
+
+    bot.run(&alice, "!new_poll mittag");
+    bot.run(&alice, "!new_item curry");
+    bot.run(&alice, "!new_voter");
+    bot.run(&bob, "!new_voter");
+    bot.run(&alice, "!poll_set mittag eligibility=invited");
+
+    let reply = bot.run_single(&alice, "!invite mittag <@U1>");
+    assert!(reply.starts_with("'alice' darf jetzt bei der Umfrage 'mittag' abstimmen."), reply);
+
+    let reply = bot.run_single(&alice, "!start_poll mittag");
+    assert!(reply.starts_with("Umfrage 'mittag' gestartet. Abstimmen darf nur der eingeladene Wähler <@U1>."), reply);
+
+    let reply = bot.run_single(&bob, "!vote mittag curry +");
+    assert!(reply.starts_with("Du darfst bei der Umfrage 'mittag' nicht abstimmen!"), reply);
+
+    let reply = bot.run_single(&alice, "!vote mittag curry +");
+    assert!(reply.starts_with("Stimme gezählt"), reply);
+
+    // Bob is registered, but not expected to vote
+    let results = bot.run_single(&alice, "!show_poll_results mittag");
+    assert!(results.contains("\nBeteiligung: 1 von 1 Wähler (100%)\n"), results);
+
+    // Only the registered members of the channel may vote in a channel poll
+    let carol = user("U3", "carol");
+    bot.run(&carol, "!new_voter");
+    bot.transport.set_channel_members(CHANNEL, &["U1", "U2", "U4"]);
+    bot.run(&alice, "!new_poll abend");
+    bot.run(&alice, "!new_item pizza");
+    bot.run(&alice, "!poll_set abend eligibility=channel");
+    bot.run(&alice, "!start_poll abend");
+    bot.run(&alice, "!vote abend pizza +");
+
+    let results = bot.run_single(&alice, "!show_poll_results abend");
+    assert!(results.contains("\nBeteiligung: 1 von 2 Wählern (50%)\n"), results);
+}
+
+#[test]
+fn poll_concludes_itself_once_everyone_voted() {
+    let alice = user("U1", "alice");
+    let bob = user("U2", "bob");
+    let bot = TestBot::new();
+
+    bot.run(&alice, "!new_poll mittag");
+    bot.run(&alice, "!new_item curry");
+    bot.run(&alice, "!poll_set mittag auto_conclude=all-voted");
+    bot.run(&alice, "!start_poll mittag");
+    bot.run(&alice, "!new_voter");
+    bot.run(&bob, "!new_voter");
+
+    let replies = bot.run(&alice, "!vote mittag curry +");
+    assert_eq!(replies.len(), 1);
+
+    let replies = bot.run(&bob, "!vote mittag curry +");
+    assert_eq!(replies.len(), 4);
+    assert_eq!(replies[1], "Alle haben bei der Umfrage 'mittag' abgestimmt.");
+    assert!(replies[2].starts_with("Umfrage beendet 'mittag'."), replies[2].clone());
+    assert_eq!(replies[3], ":trophy: 'curry' gewinnt die Umfrage 'mittag' mit +2 (100% Zustimmung).");
+}
+
+#[test]
+fn items_are_listed_with_details_and_filtered_by_tag() {
+    let alice = user("U1", "alice");
+    let bot = TestBot::new();
+
+    bot.run(&alice, "!new_item curry");
+    bot.run(&alice, "!new_item pizza");
+
+    let replies = bot.run(&alice, "!item_set curry category=indisch price=$$ vegetarian=ja tags=veggie,scharf address=Hauptstraße 1");
+    assert_eq!(replies.len(), 5);
+    assert!(replies[4].starts_with("'address' von 'curry' ist jetzt 'Hauptstraße 1'."), replies[4].clone());
+
+    let list = bot.run_single(&alice, "!list_items tag=veggie");
+    assert_eq!(list, "Orte 1 bis 1 von 1 (Seite 1 von 1):\n1. curry (indisch, $$, vegetarisch, #veggie, #scharf, Hauptstraße 1)\n");
+
+    let list = bot.run_single(&alice, "!list_items");
+    assert!(list.starts_with("Orte 1 bis 2 von 2 (Seite 1 von 1):\n1. pizza\n"), list);
+}
+
+#[test]
+fn secret_votes_are_left_out_of_the_voter_stats() {
+    let alice = user("U1", "alice");
+    let bob = user("U2", "bob");
+    let carol = user("U3", "carol");
+    let bot = bot_with_running_poll(&[&alice, &bob, &carol]);
+
+    bot.run(&alice, "!vote mittag curry +");
+    bot.run(&bob, "!vote mittag curry +");
+    bot.run(&carol, "!vote mittag curry +");
+    bot.run(&alice, "!conclude_poll mittag");
+
+    bot.run(&alice, "!new_poll geheim");
+    bot.run(&alice, "!poll_set geheim anonymous=ja");
+    bot.run(&alice, "!new_proposal geheim curry");
+    bot.run(&alice, "!start_poll geheim");
+    bot.run_in(DIRECT_CHANNEL, &alice, "!vote geheim curry +");
+    bot.run_in(DIRECT_CHANNEL, &carol, "!vote geheim curry +");
+    // Against the result, but nobody may learn that
+    bot.run_in(DIRECT_CHANNEL, &bob, "!vote geheim curry -");
+    bot.run(&alice, "!conclude_poll geheim");
+
+    let stats = bot.run_single(&alice, "!stats voters");
+    assert_eq!(stats, "Beteiligung pro Wähler:\n\
+                       1. alice: 1 von 1 Umfragen (100%), 0 von 1 Stimmen gegen das Ergebnis\n\
+                       2. bob: 1 von 1 Umfragen (100%), 0 von 1 Stimmen gegen das Ergebnis\n\
+                       3. carol: 1 von 1 Umfragen (100%), 0 von 1 Stimmen gegen das Ergebnis\n");
+
+    let reply = bot.run_single(&alice, "!stats wetter");
+    assert!(reply.starts_with("Ich kenne `!stats`, `!stats items`"), reply);
+}
+
+#[test]
+fn lists_are_filtered_sorted_and_paged() {
+    let alice = user("U1", "alice");
+    let bot = TestBot::new();
+
+    for poll_name in &["mittag", "abend", "mittwoch", "brunch"] {
+        bot.run(&alice, &format!("!new_poll {}", poll_name));
+    }
+
+    bot.run(&alice, "!start_poll abend");
+
+    let list = bot.run_single(&alice, "!list_polls status=stopped sort=name limit=2 page=2");
+    assert_eq!(list, "Umfragen 3 bis 3 von 3 (Seite 2 von 2):\n3. mittwoch (gestoppt)\n");
+
+    let list = bot.run_single(&alice, "!list_polls search=MITT sort=oldest");
+    assert_eq!(list, "Umfragen 1 bis 2 von 2 (Seite 1 von 1):\n1. mittag (gestoppt)\n2. mittwoch (gestoppt)\n");
+
+    let reply = bot.run_single(&alice, "!list_polls page=3");
+    assert!(reply.starts_with("Ich finde keine passenden Umfragen!"), reply);
+
+    let reply = bot.run_single(&alice, "!list_items limit=100");
+    assert!(reply.starts_with("Ich kann nicht nach 'limit=100' filtern."), reply);
+
+    let reply = bot.run_single(&alice, "!list_polls page=999999999999999999 limit=50");
+    assert!(reply.starts_with("Ich kann nicht nach 'page=999999999999999999' filtern."), reply);
+
+    let reply = bot.run_single(&alice, "!list_items page=184467440737095516 limit=50");
+    assert!(reply.starts_with("Ich finde keine passenden Orte!"), reply);
+
+    // Wildcards of SQL are searched for literally
+    bot.run(&alice, "!new_poll team_lunch");
+    bot.run(&alice, "!new_poll teamxlunch");
+
+    let list = bot.run_single(&alice, "!list_polls search=m_l");
+    assert_eq!(list, "Umfragen 1 bis 1 von 1 (Seite 1 von 1):\n1. team_lunch (gestoppt)\n");
+
+    bot.run(&alice, "!new_item curry_haus");
+    bot.run(&alice, "!new_item curryxhaus");
+    bot.run(&alice, "!item_set curryxhaus tags=scharf");
+
+    let list = bot.run_single(&alice, "!list_items search=y_h");
+    assert!(list.starts_with("Orte 1 bis 1 von 1 (Seite 1 von 1):\n"), list.clone());
+    assert!(list.contains("curry_haus") && !list.contains("curryxhaus"), list);
+
+    let reply = bot.run_single(&alice, "!list_items tag=%");
+    assert!(reply.starts_with("Ich finde keine passenden Orte!"), reply);
+}
+
+#[test]
+fn names_are_matched_ignoring_case_accents_and_aliases() {
+    let alice = user("U1", "alice");
+    let bot = bot_with_running_poll(&[&alice]);
+
+    bot.run(&alice, "!new_item café-müller");
+    bot.run(&alice, "!item_alias curry Curry-Haus");
+
+    let reply = bot.run_single(&alice, "!vote Mittag CAFE-MULLER +");
+    assert!(reply.starts_with("Stimme gezählt für 'mittag' bei 'café-müller'"), reply);
+
+    let reply = bot.run_single(&alice, "!vote mittag curry-haus +");
+    assert!(reply.starts_with("Stimme gezählt für 'mittag' bei 'curry'"), reply);
+
+    let reply = bot.run_single(&alice, "!vote mittag piza +");
+    assert!(reply.starts_with("Ich kann die Umfrage 'mittag' oder den Ort 'piza' nicht finden! Meintest du 'pizza'?"), reply);
+}
+
+#[test]
+fn names_from_before_normalizing_are_filled_in() {
+    let dir = temp_dir("normalize");
+    let database_url = dir.join("lunch.sqlite").to_string_lossy().into_owned();
+    let alice = user("U1", "alice");
+
+    TestBot::with_database(&database_url).run(&alice, "!new_item café-müller");
+
+    let db_conn = SqliteConnection::establish(&database_url).unwrap();
+    db_conn.execute("UPDATE items SET normalized_name = NULL").unwrap();
+
+    let repo = SqliteRepository::new(db_conn);
+    assert!(item::find_item_by_name(&repo, "CAFE-MULLER").is_none());
+
+    repo.fill_normalized_names();
+    assert_eq!(item::find_item_by_name(&repo, "CAFE-MULLER").map(|item| item.name), Some(String::from("café-müller")));
+
+    fs::remove_dir_all(&dir).expect("Cannot remove temporary directory.");
+}
+
+#[test]
+fn items_are_picked_and_suggested_for_a_poll() {
+    let alice = user("U1", "alice");
+    let bot = TestBot::new();
+
+    bot.run(&alice, "!new_poll mittag");
+    bot.run(&alice, "!new_item curry");
+    bot.run(&alice, "!new_item pizza");
+    bot.run(&alice, "!item_set curry tags=veggie");
+
+    let reply = bot.run_single(&alice, "!pick tag=veggie");
+    assert!(reply.starts_with("Wie wäre es mit 'curry'?"), reply);
+
+    let reply = bot.run_single(&alice, "!pick tag=sushi");
+    assert!(reply.starts_with("Ich finde keinen passenden Ort!"), reply);
+
+    let reply = bot.run_single(&alice, "!suggest count=5 poll=mittag");
+    assert!(reply.starts_with("Meine 2 Vorschläge:\n1. "), reply.clone());
+    assert!(reply.contains("(noch nie gewonnen)"), reply.clone());
+    assert!(reply.contains("Die Vorschläge stehen jetzt in der Umfrage 'mittag' zur Wahl."), reply);
+
+    let reply = bot.run_single(&alice, "!new_proposal mittag pizza");
+    assert!(reply.starts_with("Der Vorschlag für 'mittag' bei 'pizza' existiert bereits!"), reply);
+}
+
+#[test]
+fn suggestion_weight_prefers_winners_but_not_recent_or_downvoted_ones() {
+    let weight = |wins, days_since_last_win, recent_downvotes| suggest::weight(&ItemHistory {
+        wins: wins,
+        days_since_last_win: days_since_last_win,
+        recent_downvotes: recent_downvotes
+    });
+
+    assert_eq!(weight(0, None, 0), 1.0);
+    assert_eq!(weight(2, None, 0), 3.0);
+    assert_eq!(weight(1, Some(30), 0), 2.0);
+    assert_eq!(weight(1, Some(7), 0), 1.0);
+    assert_eq!(weight(1, Some(0), 0), 0.1);
+    assert_eq!(weight(0, None, 3), 0.25);
+}
+
+#[test]
+fn suggestions_follow_the_history_of_the_items() {
+    let alice = user("U1", "alice");
+    let bob = user("U2", "bob");
+    let bot = bot_with_running_poll(&[&alice, &bob]);
+
+    bot.run(&alice, "!new_item sushi");
+    bot.run(&alice, "!vote mittag curry +");
+    bot.run(&bob, "!vote mittag curry +");
+    bot.run(&bob, "!vote mittag pizza -");
+    bot.run(&alice, "!conclude_poll mittag");
+
+    let id = |name: &str| bot.repo.find_item_by_name(name).unwrap().id;
+    let histories = suggest::item_histories(&bot.repo);
+
+    assert_eq!(histories[&id("curry")], ItemHistory { wins: 1, days_since_last_win: Some(0), recent_downvotes: 0 });
+    assert_eq!(histories[&id("pizza")], ItemHistory { wins: 0, days_since_last_win: None, recent_downvotes: 1 });
+    assert!(histories.get(&id("sushi")).is_none());
+
+    // Weights 0.1 (curry), 0.5 (pizza) and 1.0 (sushi)
+    let mut rng = StdRng::from_seed(&[42][..]);
+    let mut picks: Vec<String> = Vec::new();
+
+    for _ in 0..1000 {
+        let suggestion = suggest::suggest(&bot.repo, bot.repo.find_items(), 1, &mut rng).remove(0);
+        picks.push(suggestion.item.name);
+    }
+
+    let count = |name: &str| picks.iter().filter(|pick| *pick == name).count();
+    assert!(count("sushi") > count("pizza") && count("pizza") > count("curry"), "{:?}", (count("sushi"), count("pizza"), count("curry")));
+    assert!(count("curry") > 0);
+
+    // The bot's generator is seeded, so the same commands give the same suggestions
+    let replay = || {
+        let bot = TestBot::new();
+        bot.run(&alice, "!new_item curry");
+        bot.run(&alice, "!new_item pizza");
+        bot.run(&alice, "!new_item sushi");
+        bot.run_single(&alice, "!suggest count=2")
+    };
+
+    assert_eq!(replay(), replay());
+}
+
+#[test]
+fn stats_report_wins_turnout_and_trend() {
+    let alice = user("U1", "alice");
+    let bob = user("U2", "bob");
+    let bot = bot_with_running_poll(&[&alice, &bob]);
+
+    let reply = bot.run_single(&alice, "!stats");
+    assert!(reply.starts_with("Es gibt noch keine beendeten Umfragen"), reply);
+
+    bot.run(&alice, "!vote mittag curry +");
+    bot.run(&alice, "!vote mittag pizza +");
+    bot.run(&bob, "!vote mittag curry +");
+    bot.run(&bob, "!vote mittag pizza -");
+    bot.run(&alice, "!conclude_poll mittag");
+
+    let stats = bot.run_single(&alice, "!stats");
+    assert_eq!(stats, "Statistik über 1 beendete Umfrage:\nMeiste Siege: curry (1)\nDurchschnittliche Beteiligung: 2.0 von 2 Wählern (100%)\n");
+
+    let stats = bot.run_single(&alice, "!stats items");
+    assert_eq!(stats, "Siege pro Ort:\n1. curry: 1 von 1 Umfragen gewonnen (100%)\n2. pizza: 0 von 1 Umfragen gewonnen (0%)\n");
+
+    let stats = bot.run_single(&alice, "!stats voters");
+    assert_eq!(stats, "Beteiligung pro Wähler:\n1. alice: 1 von 1 Umfragen (100%), 0 von 2 Stimmen gegen das Ergebnis\n2. bob: 1 von 1 Umfragen (100%), 0 von 2 Stimmen gegen das Ergebnis\n");
+
+    let stats = bot.run_single(&alice, "!stats item curry");
+    assert!(stats.starts_with("Verlauf von 'curry':\nmittag ("), stats.clone());
+    assert!(stats.ends_with("): +2 :trophy:\nTendenz: gleichbleibend\n"), stats);
+}
+
+#[test]
+fn polls_are_exported_as_csv_and_json_without_secret_voters() {
+    let alice = user("U1", "alice");
+    let bot = bot_with_running_poll(&[&alice]);
+
+    bot.run(&alice, "!item_set curry tags=veggie,scharf");
+    bot.run(&alice, "!vote mittag curry +");
+
+    let reply = bot.run_single(&alice, "!export mittag csv");
+    assert!(reply.starts_with("Hier ist der Export 'lunch-mittag.csv'."), reply);
+
+    let uploads = bot.uploads();
+    assert_eq!(uploads.len(), 1);
+
+    let lines: Vec<&str> = uploads[0].1.lines().collect();
+    assert_eq!(lines[0], "poll,status,anonymous,started_at,concluded_at,\
+                          visibility,budget,max_approvals,eligibility,quorum,auto_conclude,creator_slack_id,channel_id,\
+                          item,category,address,price_level,menu_url,vegetarian,vegan,tags,voter,voter_slack_id,weight");
+    assert!(lines[1].starts_with("mittag,IN_PROGRESS,false,"), lines[1].to_owned());
+    assert!(lines[1].ends_with(",,ALWAYS,,,REGISTERED,,OFF,U1,C0LUNCH,curry,,,,,false,false,\"veggie,scharf\",alice,U1,1"), lines[1].to_owned());
+    assert!(lines[2].ends_with(",,ALWAYS,,,REGISTERED,,OFF,U1,C0LUNCH,pizza,,,,,false,false,,,,"), lines[2].to_owned());
+
+    bot.run(&alice, "!new_poll geheim");
+    bot.run(&alice, "!poll_set geheim anonymous=ja");
+    bot.run(&alice, "!new_proposal geheim curry");
+    bot.run(&alice, "!start_poll geheim");
+    bot.run_in(DIRECT_CHANNEL, &alice, "!vote geheim curry +");
+
+    bot.run(&alice, "!export geheim json");
+    let export: serde_json::Value = serde_json::from_str(&bot.uploads()[0].1).unwrap();
+    assert_eq!(export["polls"][0]["eligibility"], "REGISTERED");
+    assert_eq!(export["polls"][0]["budget"], serde_json::Value::Null);
+    assert_eq!(export["polls"][0]["channel_id"], "C0LUNCH");
+
+    let vote = &export["polls"][0]["proposals"][0]["votes"][0];
+    assert_eq!(vote["weight"], 1);
+    assert!(vote.get("voter").is_none());
+
+    // Poll names do not leave the directory the export is written to
+    bot.run(&alice, "!new_poll ../../etc/passwd");
+    let reply = bot.run_single(&alice, "!export ../../etc/passwd csv");
+    assert!(reply.starts_with("Hier ist der Export 'lunch-______etc_passwd.csv'."), reply);
+
+    let reply = bot.run_single(&alice, "!export all xml");
+    assert!(reply.starts_with("Ich kann nur als csv oder json exportieren"), reply);
+}
+
+#[test]
+fn items_are_imported_from_csv_with_a_report() {
+    let alice = user("U1", "alice");
+    let bot = TestBot::new();
+
+    bot.run(&alice, "!new_item curry");
+
+    let reply = bot.run_single(&alice, "!import_items\n```\nname,tags,price\nSushi Bar,\"fisch,roh\",3\ncurry,,\n,x,\nfalafel,,9\n```");
+    assert!(reply.starts_with("Import fertig: 1 angelegt, 1 schon vorhanden, 2 ungültig. Ungültige Einträge: 3, 4."), reply);
+
+    let list = bot.run_single(&alice, "!list_items tag=fisch");
+    assert_eq!(list, "Orte 1 bis 1 von 1 (Seite 1 von 1):\n1. sushi bar ($$$, #fisch, #roh)\n");
+}
+
+#[test]
+fn exported_history_is_imported_again() {
+    let alice = user("U1", "alice");
+    let bob = user("U2", "bob");
+    let bot = bot_with_running_poll(&[&alice, &bob]);
+
+    bot.run(&alice, "!vote mittag curry +");
+    bot.run(&bob, "!vote mittag curry +");
+    bot.run(&bob, "!vote mittag pizza -");
+    bot.run(&alice, "!conclude_poll mittag");
+
+    bot.run(&alice, "!new_poll abend");
+    bot.run(&alice, "!poll_set abend visibility=after-vote budget=3 max_approvals=2 eligibility=open quorum=2 auto_conclude=majority");
+    bot.run(&alice, "!new_proposal abend pizza");
+
+    // Votes of secret polls cannot be restored, as their voters are not exported
+    bot.run(&alice, "!new_poll geheim");
+    bot.run(&alice, "!poll_set geheim anonymous=ja");
+    bot.run(&alice, "!new_proposal geheim curry");
+    bot.run(&alice, "!start_poll geheim");
+    bot.run_in(DIRECT_CHANNEL, &alice, "!vote geheim curry +");
+
+    let results = bot.run_single(&alice, "!show_poll_results mittag");
+    let settings = |repo: &SqliteRepository, name: &str| {
+        let poll = repo.find_poll_by_name(name).unwrap();
+        (poll.anonymous, poll.visibility, poll.budget, poll.max_approvals, poll.eligibility, poll.quorum, poll.auto_conclude, poll.creator_slack_id, poll.channel_id)
+    };
+
+    for format in &["csv", "json"] {
+        bot.run(&alice, &format!("!export all {}", format));
+        let export = bot.uploads().remove(0).1;
+
+        let restored = TestBot::new();
+        let report = import::import_history(&restored.repo, &export);
+        assert_eq!((report.created, report.skipped, report.invalid.len()), (3, 0, 0));
+
+        assert_eq!(restored.run_single(&alice, "!show_poll_results mittag"), results);
+
+        for name in &["mittag", "abend", "geheim"] {
+            assert_eq!(settings(&restored.repo, name), settings(&bot.repo, name), "{} from {}", name, format);
+        }
+
+        let geheim = restored.repo.find_poll_by_name("geheim").unwrap();
+        let proposals = restored.repo.find_poll_results(&geheim);
+        assert_eq!(proposals.len(), 1);
+        assert!(proposals[0].1.is_empty());
+
+        let report = import::import_history(&restored.repo, &export);
+        assert_eq!((report.created, report.skipped), (0, 3));
+    }
+}
+
+#[test]
+fn polls_concluded_long_ago_are_pruned() {
+    let alice = user("U1", "alice");
+    let bot = bot_with_running_poll(&[&alice]);
+
+    bot.run(&alice, "!vote mittag curry +");
+    bot.run(&alice, "!conclude_poll mittag");
+    bot.run(&alice, "!new_poll abend");
+
+    assert_eq!(backup::prune_polls(&bot.repo, 30, None), Ok(0));
+
+    let poll = bot.repo.find_poll_by_name("mittag").unwrap();
+    bot.repo.update_poll_timestamps(poll.id, Some("2017-06-01 12:00:00"), Some("2017-06-01 13:00:00"));
+
+    assert_eq!(backup::prune_polls(&bot.repo, 30, None), Ok(1));
+
+    let reply = bot.run_single(&alice, "!poll mittag");
+    assert!(reply.starts_with("Ich kann keine Umfrage namens 'mittag' finden!"), reply);
+    assert!(bot.repo.find_poll_by_name("abend").is_some());
+}
+
+#[test]
+fn database_is_restored_from_a_backup() {
+    let dir = temp_dir("backup");
+    let database_url = dir.join("lunch.sqlite").to_string_lossy().into_owned();
+    let alice = user("U1", "alice");
+
+    let backup_file = {
+        let bot = TestBot::with_database(&database_url);
+        bot.run(&alice, "!new_voter");
+        bot.run(&alice, "!new_item curry");
+        bot.run(&alice, "!new_poll mittag");
+
+        let backup_file = backup::backup(&database_url, &dir.join("backups")).expect("Cannot back up database.");
+
+        bot.run(&alice, "!new_poll abend");
+        bot.run(&alice, "!new_item pizza");
+        backup_file
+    };
+
+    // Not while someone else is writing
+    let db_conn = SqliteConnection::establish(&database_url).unwrap();
+    db_conn.execute("BEGIN EXCLUSIVE").unwrap();
+    assert!(backup::restore(&backup_file, &database_url).unwrap_err().ends_with("is in use, stop the bot first."));
+    db_conn.execute("ROLLBACK").unwrap();
+
+    assert_eq!(backup::restore(&backup_file, &database_url), Ok(()));
+
+    let bot = TestBot::with_database(&database_url);
+    assert!(bot.repo.find_poll_by_name("mittag").is_some());
+    assert!(bot.repo.find_poll_by_name("abend").is_none());
+    assert!(bot.repo.find_item_by_name("curry").is_some());
+    assert!(bot.repo.find_item_by_name("pizza").is_none());
+
+    fs::remove_dir_all(&dir).expect("Cannot remove temporary directory.");
+}
+
+#[test]
+fn admins_see_the_audit_log_of_a_poll() {
+    let alice = user("U1", "alice");
+    let bob = user("U2", "bob");
+    let bot = bot_with_running_poll(&[&alice, &bob]).with_admins(&["U2"]);
+
+    bot.run(&alice, "!vote mittag curry +");
+    assert!(!bot.invoke_in(CHANNEL, &alice, "!vote mittag curry"));
+    bot.transport.take_messages();
+    bot.run(&alice, "!show_poll_results mittag");
+    bot.run(&alice, "!conclude_poll mittag");
+
+    let reply = bot.run_single(&alice, "!audit mittag");
+    assert!(reply.starts_with("Nur Admins dürfen ins Protokoll schauen!"), reply);
+
+    let log = bot.run_single(&bob, "!audit mittag");
+    let lines: Vec<&str> = log.lines().collect();
+    assert_eq!(lines.len(), 5, "{}", log);
+    assert_eq!(lines[0], "Protokoll von 'mittag':");
+    assert!(lines[1].ends_with(" <@U1>: `!new_poll mittag` → poll-created"), lines[1].to_owned());
+    assert!(lines[2].ends_with(" <@U1>: `!start_poll mittag` → poll-started"), lines[2].to_owned());
+    assert!(lines[3].ends_with(" <@U1>: `!vote mittag curry +` → vote-counted"), lines[3].to_owned());
+    assert!(lines[4].ends_with(" <@U1>: `!conclude_poll mittag` → poll-concluded"), lines[4].to_owned());
+
+    bot.run(&alice, "!new_poll geheim");
+    bot.run(&alice, "!poll_set geheim anonymous=ja");
+    bot.run(&alice, "!new_proposal geheim curry");
+    bot.run(&alice, "!start_poll geheim");
+    bot.run_in(DIRECT_CHANNEL, &alice, "!vote geheim curry +");
+
+    let log = bot.run_single(&bob, "!audit geheim");
+    assert!(log.contains(" jemand: `!vote geheim` → vote-counted\n"), log.clone());
+    assert!(!log.contains("<@U1>: `!vote"), log);
+
+    let poll = bot.repo.find_poll_by_name("geheim").unwrap();
+    let vote = bot.repo.find_audit_entries_by_poll(poll.id).into_iter()
+        .find(|entry| entry.command == "vote")
+        .expect("Vote is not recorded.");
+    assert_eq!((vote.slack_id, vote.voter_id, vote.channel_id), (None, None, None));
+    assert_eq!(vote.parameters, "geheim");
+
+    bot.run(&alice, "!conclude_poll geheim");
+    bot.repo.update_poll_timestamps(poll.id, Some("2017-06-01 12:00:00"), Some("2017-06-01 13:00:00"));
+    assert_eq!(backup::prune_polls(&bot.repo, 30, None), Ok(1));
+    assert!(bot.repo.find_audit_entries_by_poll(poll.id).is_empty());
+}
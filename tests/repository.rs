@@ -0,0 +1,185 @@
+extern crate diesel;
+extern crate slackbot_rs;
+
+use diesel::Connection;
+use diesel::pg::PgConnection;
+
+use std::env;
+
+use slackbot_rs::models::{ItemFilter, ItemOption, ItemStats, Pagination, PollFilter, PollOption, PollStatus, VoterStats};
+use slackbot_rs::repository::{PgRepository, PollRepository, SqliteRepository};
+
+/// Checks of the handwritten queries, each expecting an empty database.
+const CHECKS: &'static [fn(&PollRepository)] = &[
+    statistics_count_concluded_polls_only,
+    poll_results_list_proposals_with_their_votes,
+    searches_match_wildcards_literally_and_ignore_case
+];
+
+#[test]
+fn queries_work_on_sqlite() {
+    for check in CHECKS {
+        check(&SqliteRepository::in_memory());
+    }
+}
+
+/// Runs against the PostgreSQL database in `TEST_DATABASE_URL`, e.g. the
+/// `postgres-test` service of docker-compose. Everything in its `public`
+/// schema is dropped before each check. One test for all checks, so they do
+/// not empty the database under each other.
+#[test]
+fn queries_work_on_postgres() {
+    let database_url = match env::var("TEST_DATABASE_URL") {
+        Ok(database_url) => database_url,
+        Err(_) => {
+            println!("[Info] TEST_DATABASE_URL is not set, skipping PostgreSQL.");
+            return;
+        }
+    };
+
+    for check in CHECKS {
+        let db_conn = PgConnection::establish(&database_url)
+            .expect(&format!("Error connecting to {}", database_url));
+
+        db_conn.execute("DROP SCHEMA public CASCADE; CREATE SCHEMA public")
+            .expect("Cannot empty test database.");
+
+        check(&PgRepository::establish(&database_url));
+    }
+}
+
+fn vote(repo: &PollRepository, poll_name: &str, item_name: &str, slack_id: &str, weight: i32) {
+    let poll = repo.find_poll_by_name(poll_name).unwrap();
+    let item = repo.find_item_by_name(item_name).unwrap();
+    let voter = repo.find_voter_by_slack_id(slack_id).unwrap();
+
+    repo.insert_proposal(poll.id, item.id);
+    let proposal = repo.find_proposal_by_poll_id_and_item_id(poll.id, item.id).unwrap();
+
+    assert!(repo.upsert_vote(voter.id, proposal.id, weight));
+}
+
+/// Curry wins `mittag`, pizza wins the secret poll `geheim` and sushi is only
+/// proposed in the running poll `abend`.
+fn create_history(repo: &PollRepository) {
+    for &(slack_id, name) in &[("U1", "alice"), ("U2", "bob"), ("U3", "carol")] {
+        repo.create_voter(slack_id, name);
+    }
+
+    for name in &["curry", "pizza", "sushi"] {
+        repo.create_item(name);
+    }
+
+    for name in &["mittag", "geheim", "abend"] {
+        repo.create_poll(name, PollStatus::InProgress, Some("U1"), Some("C0LUNCH"));
+    }
+
+    let geheim = repo.find_poll_by_name("geheim").unwrap();
+    repo.update_poll_option(geheim.id, &PollOption::Anonymous(true));
+
+    vote(repo, "mittag", "curry", "U1", 1);
+    vote(repo, "mittag", "curry", "U2", 1);
+    vote(repo, "mittag", "pizza", "U3", 1);
+    vote(repo, "mittag", "pizza", "U1", -1);
+    vote(repo, "mittag", "pizza", "U2", 1);
+    vote(repo, "geheim", "pizza", "U2", 1);
+    vote(repo, "abend", "sushi", "U1", 1);
+
+    repo.update_poll_status("mittag", PollStatus::Concluded);
+    repo.update_poll_status("geheim", PollStatus::Concluded);
+}
+
+fn statistics_count_concluded_polls_only(repo: &PollRepository) {
+    create_history(repo);
+
+    assert_eq!(repo.find_item_stats(), vec![
+        ItemStats { name: String::from("curry"), proposals: 1, wins: 1 },
+        ItemStats { name: String::from("pizza"), proposals: 2, wins: 1 }
+    ]);
+
+    // Bob's vote in the secret poll is left out
+    assert_eq!(repo.find_voter_stats(), vec![
+        VoterStats { name: String::from("alice"), polls: 1, votes: 2, contrarian_votes: 1 },
+        VoterStats { name: String::from("bob"), polls: 1, votes: 2, contrarian_votes: 0 },
+        VoterStats { name: String::from("carol"), polls: 1, votes: 1, contrarian_votes: 0 }
+    ]);
+
+    assert_eq!(repo.count_participants_per_concluded_poll(), vec![3, 1]);
+
+    let pizza = repo.find_item_by_name("pizza").unwrap();
+    let trend: Vec<_> = repo.find_item_trend(&pizza)
+        .into_iter()
+        .map(|point| (point.poll_name, point.concluded_at.is_some(), point.score, point.won))
+        .collect();
+    assert_eq!(trend, vec![(String::from("mittag"), true, 1, false), (String::from("geheim"), true, 1, true)]);
+}
+
+fn poll_results_list_proposals_with_their_votes(repo: &PollRepository) {
+    create_history(repo);
+
+    let poll = repo.find_poll_by_name("mittag").unwrap();
+    let results: Vec<_> = repo.find_poll_results(&poll)
+        .into_iter()
+        .map(|(item, votes)| {
+            let votes: Vec<_> = votes.into_iter().map(|(vote, voter)| (voter.name, vote.weight)).collect();
+            (item.name, votes)
+        })
+        .collect();
+
+    assert_eq!(results, vec![
+        (String::from("curry"), vec![(String::from("alice"), 1), (String::from("bob"), 1)]),
+        (String::from("pizza"), vec![(String::from("carol"), 1), (String::from("alice"), -1), (String::from("bob"), 1)])
+    ]);
+
+    // Proposals without votes are listed as well
+    repo.create_item("tapas");
+    let tapas = repo.find_item_by_name("tapas").unwrap();
+    repo.insert_proposal(poll.id, tapas.id);
+
+    let results = repo.find_poll_results(&poll);
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[2].0.name, "tapas");
+    assert!(results[2].1.is_empty());
+}
+
+fn searches_match_wildcards_literally_and_ignore_case(repo: &PollRepository) {
+    for name in &["team_lunch", "teamxlunch", "Team-Abend"] {
+        repo.create_poll(name, PollStatus::Stopped, None, None);
+    }
+
+    let search = |text: &str| {
+        let filter = PollFilter { search: Some(text.to_owned()), ..PollFilter::default() };
+        let (polls, total) = repo.find_polls_page(&filter, &Pagination::default());
+        let names: Vec<_> = polls.into_iter().map(|poll| poll.name).collect();
+
+        (names, total)
+    };
+
+    assert_eq!(search("m_l"), (vec![String::from("team_lunch")], 1));
+    assert_eq!(search("team"), (vec![String::from("Team-Abend"), String::from("teamxlunch"), String::from("team_lunch")], 3));
+    assert_eq!(search("%"), (Vec::new(), 0));
+
+    for name in &["curry_haus", "curryxhaus"] {
+        repo.create_item(name);
+    }
+
+    let curryxhaus = repo.find_item_by_name("curryxhaus").unwrap();
+    repo.update_item_option(curryxhaus.id, &ItemOption::parse("tags", "scharf,100%").unwrap());
+
+    let find_items = |key: &str, value: &str| {
+        let mut filter = ItemFilter::default();
+        assert!(filter.add(key, value));
+
+        let names: Vec<_> = repo.find_items_matching(&filter).into_iter().map(|item| item.name).collect();
+        let (page, total) = repo.find_items_page(&filter, &Pagination::default());
+        assert_eq!(page.len() as i64, total);
+
+        names
+    };
+
+    assert_eq!(find_items("search", "y_h"), vec![String::from("curry_haus")]);
+    assert_eq!(find_items("search", "CURRY"), vec![String::from("curry_haus"), String::from("curryxhaus")]);
+    assert_eq!(find_items("tag", "scharf"), vec![String::from("curryxhaus")]);
+    assert_eq!(find_items("tag", "100%"), vec![String::from("curryxhaus")]);
+    assert_eq!(find_items("tag", "%"), Vec::<String>::new());
+}